@@ -0,0 +1,154 @@
+use serde_json::Value;
+use solana_sdk::hash::{hash, Hash};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// How long an emulation result is served from the cache
+const EMULATION_CACHE_TTL: Duration = Duration::from_secs(5);
+
+/// Maximum number of cached emulation results
+const EMULATION_CACHE_CAPACITY: usize = 1024;
+
+/// Hash of the serialized emulation request and the slot it is emulated at
+pub type EmulationCacheKey = (Hash, u64);
+
+struct Entry {
+    created: Instant,
+    last_used: u64,
+    value: Value,
+}
+
+#[derive(Default)]
+struct Entries {
+    values: HashMap<EmulationCacheKey, Entry>,
+    /// Keys by the time of the last use, the least recently used first
+    recency: BTreeMap<u64, EmulationCacheKey>,
+    time: u64,
+}
+
+/// Least recently used results of emulations at rooted slots.
+/// The state of a rooted slot does not change, so the result can be reused.
+pub struct EmulationCache {
+    entries: Mutex<Entries>,
+    capacity: usize,
+    ttl: Duration,
+}
+
+impl Default for EmulationCache {
+    fn default() -> Self {
+        Self::new(EMULATION_CACHE_CAPACITY, EMULATION_CACHE_TTL)
+    }
+}
+
+impl EmulationCache {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            entries: Mutex::new(Entries::default()),
+            capacity,
+            ttl,
+        }
+    }
+
+    pub fn key(request: &[u8], slot: u64) -> EmulationCacheKey {
+        (hash(request), slot)
+    }
+
+    pub fn get(&self, key: &EmulationCacheKey) -> Option<Value> {
+        let mut entries = self.lock();
+        let Entries {
+            values,
+            recency,
+            time,
+        } = &mut *entries;
+
+        let entry = values.get_mut(key)?;
+        recency.remove(&entry.last_used);
+
+        if entry.created.elapsed() >= self.ttl {
+            values.remove(key);
+            return None;
+        }
+
+        *time += 1;
+        entry.last_used = *time;
+        recency.insert(*time, *key);
+
+        Some(entry.value.clone())
+    }
+
+    /// Evicts the least recently used results if the cache is full
+    pub fn insert(&self, key: EmulationCacheKey, value: Value) {
+        let mut entries = self.lock();
+        let Entries {
+            values,
+            recency,
+            time,
+        } = &mut *entries;
+
+        *time += 1;
+        let entry = Entry {
+            created: Instant::now(),
+            last_used: *time,
+            value,
+        };
+
+        if let Some(replaced) = values.insert(key, entry) {
+            recency.remove(&replaced.last_used);
+        }
+        recency.insert(*time, key);
+
+        while values.len() > self.capacity {
+            let Some((_, oldest)) = recency.pop_first() else {
+                break;
+            };
+            values.remove(&oldest);
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<Entries> {
+        self.entries.lock().expect("emulation cache poisoned")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn cache_hit_and_miss() {
+        let cache = EmulationCache::default();
+        let key = EmulationCache::key(b"request", 100);
+        cache.insert(key, json!({ "result": "0x01" }));
+
+        assert_eq!(cache.get(&key), Some(json!({ "result": "0x01" })));
+        assert_eq!(cache.get(&EmulationCache::key(b"request", 101)), None);
+        assert_eq!(cache.get(&EmulationCache::key(b"other request", 100)), None);
+    }
+
+    #[test]
+    fn expired_results_are_not_served() {
+        let cache = EmulationCache::new(EMULATION_CACHE_CAPACITY, Duration::ZERO);
+        let key = EmulationCache::key(b"request", 100);
+        cache.insert(key, json!(1));
+
+        assert_eq!(cache.get(&key), None);
+        assert!(cache.lock().values.is_empty());
+    }
+
+    #[test]
+    fn least_recently_used_result_is_evicted() {
+        let cache = EmulationCache::new(2, EMULATION_CACHE_TTL);
+        let keys: Vec<_> = (0..3).map(|slot| EmulationCache::key(b"request", slot)).collect();
+
+        cache.insert(keys[0], json!(0));
+        cache.insert(keys[1], json!(1));
+        assert_eq!(cache.get(&keys[0]), Some(json!(0)));
+
+        cache.insert(keys[2], json!(2));
+        assert_eq!(cache.get(&keys[1]), None);
+        assert_eq!(cache.get(&keys[0]), Some(json!(0)));
+        assert_eq!(cache.get(&keys[2]), Some(json!(2)));
+    }
+}
@@ -1,14 +1,16 @@
 use actix_request_identifier::RequestId;
 use actix_web::{http::StatusCode, post, web::Json, Responder};
+use serde_json::Value;
 use std::convert::Into;
 
+use crate::api_server::emulation_cache::EmulationCache;
 use crate::api_server::handlers::process_error;
 use crate::{
     api_context, commands::emulate as EmulateCommand, context::Context,
-    types::request_models::EmulateRequestModel, NeonApiState,
+    types::request_models::EmulateRequestModel, NeonApiResult, NeonApiState,
 };
 
-use super::{parse_emulation_params, process_result};
+use super::{check_chain_id_served, parse_emulation_params, process_result};
 
 #[tracing::instrument(skip(state, request_id), fields(id = request_id.as_str()))]
 #[post("/emulate")]
@@ -17,6 +19,23 @@ pub async fn emulate(
     request_id: RequestId,
     Json(emulate_request): Json<EmulateRequestModel>,
 ) -> impl Responder {
+    // A chain id given by the request is checked before any RPC round-trip
+    if let Some(chain_id) = emulate_request.emulation_params.chain_id {
+        if let Err(e) = check_chain_id_served(&state.config.served_chain_ids, chain_id) {
+            return process_error(StatusCode::BAD_REQUEST, &e);
+        }
+    }
+
+    // Only emulations at a given slot may be cached
+    let cache_key = emulate_request.slot.and_then(|slot| {
+        let request = serde_json::to_vec(&emulate_request).ok()?;
+        Some(EmulationCache::key(&request, slot))
+    });
+
+    if let Some(value) = cache_key.and_then(|key| state.emulation_cache.get(&key)) {
+        return process_result(&NeonApiResult::<Value>::Ok(value));
+    }
+
     let tx = emulate_request.tx_params.into();
 
     let rpc_client = match api_context::build_rpc_client(&state, emulate_request.slot).await {
@@ -29,21 +48,119 @@ pub async fn emulate(
     let (token, chain, steps, accounts, solana_accounts) =
         parse_emulation_params(&state.config, &context, &emulate_request.emulation_params).await;
 
-    process_result(
-        &EmulateCommand::execute(
-            context.rpc_client,
-            state.config.evm_loader,
-            tx,
-            token,
-            chain,
-            steps,
-            state.config.commitment,
-            &accounts,
-            &solana_accounts,
-            &None,
-            None,
-        )
-        .await
-        .map_err(Into::into),
+    // Otherwise the chain id is read from the program parameters
+    if let Err(e) = check_chain_id_served(&state.config.served_chain_ids, chain) {
+        return process_error(StatusCode::BAD_REQUEST, &e);
+    }
+
+    let result = EmulateCommand::execute(
+        context.rpc_client,
+        state.config.evm_loader,
+        tx,
+        token,
+        chain,
+        steps,
+        state.config.commitment,
+        &accounts,
+        &solana_accounts,
+        &None,
+        None,
+        (&state.config).into(),
     )
+    .await
+    .map_err(Into::into);
+
+    // The rooted check is a tracer db round-trip, so it is made only for successful
+    // emulations at a given slot and never on a cache hit
+    if let (Some(key), Some(slot), Ok(value)) = (cache_key, emulate_request.slot, &result) {
+        if state.tracer_db.is_slot_rooted(slot).await.unwrap_or(false) {
+            if let Ok(value) = serde_json::to_value(value) {
+                state.emulation_cache.insert(key, value);
+            }
+        }
+    }
+
+    process_result(&result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ChDbConfig;
+    use crate::Config;
+    use actix_request_identifier::RequestIdentifier;
+    use actix_web::{test, web::Data, App};
+    use serde_json::json;
+    use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+
+    /// Any RPC or tracer db request fails, so only a cached result can be served
+    fn unreachable_state() -> NeonApiState {
+        let unreachable_url = "http://127.0.0.1:1".to_owned();
+        let config = Config {
+            evm_loader: Pubkey::new_unique(),
+            fee_payer: None,
+            commitment: CommitmentConfig::confirmed(),
+            solana_cli_config: Default::default(),
+            db_config: Some(ChDbConfig {
+                clickhouse_url: vec![unreachable_url.clone()],
+                clickhouse_user: None,
+                clickhouse_password: None,
+            }),
+            json_rpc_url: unreachable_url,
+            keypair_path: String::new(),
+            emulation_denylist: vec![],
+            served_chain_ids: vec![],
+            max_accounts_touched: None,
+            max_logs: None,
+        };
+
+        Data::new(crate::api_server::state::State::new(config))
+    }
+
+    #[actix_web::test]
+    async fn emulation_at_rooted_slot_is_served_from_cache() {
+        let state = unreachable_state();
+        let request = EmulateRequestModel {
+            slot: Some(100),
+            ..EmulateRequestModel::default()
+        };
+        let body = serde_json::to_vec(&request).unwrap();
+        let cached = json!({ "exit_status": "succeed", "result": "0x01" });
+        state
+            .emulation_cache
+            .insert(EmulationCache::key(&body, 100), cached.clone());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(state.clone())
+                .service(emulate)
+                .wrap(RequestIdentifier::with_uuid()),
+        )
+        .await;
+
+        for _ in 0..2 {
+            let request = test::TestRequest::post()
+                .uri("/emulate")
+                .insert_header(("content-type", "application/json"))
+                .set_payload(body.clone())
+                .to_request();
+            let response: Value = test::call_and_read_body_json(&app, request).await;
+
+            assert_eq!(response, json!({ "result": "success", "value": cached }));
+        }
+
+        let other_slot = serde_json::to_vec(&EmulateRequestModel {
+            slot: Some(101),
+            ..EmulateRequestModel::default()
+        })
+        .unwrap();
+        let request = test::TestRequest::post()
+            .uri("/emulate")
+            .insert_header(("content-type", "application/json"))
+            .set_payload(other_slot)
+            .to_request();
+        let response: Value = test::call_and_read_body_json(&app, request).await;
+
+        assert_eq!(response["result"], "error");
+    }
 }
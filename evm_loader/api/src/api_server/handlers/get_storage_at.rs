@@ -32,6 +32,7 @@ pub async fn get_storage_at(
             &req_params.index,
         )
         .await
+        .and_then(|value| value.encode(req_params.encoding))
         .map_err(Into::into),
     )
 }
@@ -0,0 +1,28 @@
+use crate::NeonApiState;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::web::Json;
+use actix_web::Responder;
+use serde_json::json;
+
+/// Readiness probe, responds with 503 until ClickHouse and Solana RPC are reachable
+#[tracing::instrument(skip(state))]
+#[get("/health")]
+pub async fn health(state: NeonApiState) -> impl Responder {
+    let clickhouse = state.tracer_db.ping().await.err().map(|e| e.to_string());
+    let solana = state.rpc_client.get_health().await.err().map(|e| e.to_string());
+
+    let status_code = if clickhouse.is_none() && solana.is_none() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (
+        Json(json!({
+            "clickhouse": clickhouse.unwrap_or_else(|| "ok".to_string()),
+            "solana": solana.unwrap_or_else(|| "ok".to_string()),
+        })),
+        status_code,
+    )
+}
@@ -1,5 +1,7 @@
+use actix_web::error::{InternalError, JsonPayloadError};
 use actix_web::http::StatusCode;
 use actix_web::web::Json;
+use actix_web::{HttpRequest, HttpResponse};
 use evm_loader::types::Address;
 use serde::Serialize;
 use serde_json::{json, Value};
@@ -16,8 +18,12 @@ use tracing::error;
 
 pub mod build_info;
 pub mod emulate;
+pub mod get_code;
 pub mod get_ether_account_data;
 pub mod get_storage_at;
+pub mod get_transaction_count;
+pub mod health;
+pub mod prepare_deploy;
 pub mod trace;
 
 #[derive(Debug)]
@@ -93,6 +99,18 @@ pub(crate) async fn parse_emulation_params(
     (token, chain, max_steps, accounts, solana_accounts)
 }
 
+/// Rejects chain ids outside of `served_chain_ids`, every chain is served if it is empty
+pub(crate) fn check_chain_id_served(
+    served_chain_ids: &[u64],
+    chain_id: u64,
+) -> Result<(), NeonError> {
+    if served_chain_ids.is_empty() || served_chain_ids.contains(&chain_id) {
+        return Ok(());
+    }
+
+    Err(NeonError::ChainIdNotServed(chain_id))
+}
+
 fn process_result<T: Serialize>(
     result: &NeonApiResult<T>,
 ) -> (Json<serde_json::Value>, StatusCode) {
@@ -108,6 +126,24 @@ fn process_result<T: Serialize>(
     }
 }
 
+/// Reports malformed and oversized JSON bodies in the same format as handler errors
+pub(crate) fn json_error_handler(err: JsonPayloadError, _req: &HttpRequest) -> actix_web::Error {
+    let status_code = match err {
+        JsonPayloadError::OverflowKnownLength { .. } | JsonPayloadError::Overflow { .. } => {
+            StatusCode::PAYLOAD_TOO_LARGE
+        }
+        _ => StatusCode::BAD_REQUEST,
+    };
+
+    error!("JsonPayloadError: {err}");
+    let response = HttpResponse::build(status_code).json(json!({
+        "result": "error",
+        "error": err.to_string(),
+    }));
+
+    InternalError::from_response(err, response).into()
+}
+
 fn process_error(status_code: StatusCode, e: &NeonError) -> (Json<Value>, StatusCode) {
     error!("NeonError: {e}");
     (
@@ -118,3 +154,18 @@ fn process_error(status_code: StatusCode, e: &NeonError) -> (Json<Value>, Status
         status_code,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn served_chain_ids() {
+        assert!(check_chain_id_served(&[], 111).is_ok());
+        assert!(check_chain_id_served(&[111, 112], 112).is_ok());
+        assert!(matches!(
+            check_chain_id_served(&[111, 112], 245_022_934),
+            Err(NeonError::ChainIdNotServed(245_022_934))
+        ));
+    }
+}
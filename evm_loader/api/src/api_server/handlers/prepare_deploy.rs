@@ -0,0 +1,37 @@
+use crate::api_server::handlers::process_error;
+use crate::commands::prepare_deploy as PrepareDeployCommand;
+use crate::{
+    api_context, context::Context, types::request_models::PrepareDeployRequest, NeonApiState,
+};
+use actix_request_identifier::RequestId;
+use actix_web::{http::StatusCode, post, web::Json, Responder};
+use std::convert::Into;
+
+use super::process_result;
+
+#[tracing::instrument(skip(state, request_id), fields(id = request_id.as_str()))]
+#[post("/prepare-deploy")]
+pub async fn prepare_deploy(
+    state: NeonApiState,
+    request_id: RequestId,
+    Json(req_params): Json<PrepareDeployRequest>,
+) -> impl Responder {
+    let rpc_client = match api_context::build_rpc_client(&state, req_params.slot).await {
+        Ok(rpc_client) => rpc_client,
+        Err(e) => return process_error(StatusCode::BAD_REQUEST, &e),
+    };
+
+    let context = Context::new(&*rpc_client, &state.config);
+
+    process_result(
+        &PrepareDeployCommand::execute(
+            context.rpc_client,
+            &state.config.evm_loader,
+            req_params.data.as_deref().unwrap_or_default(),
+            &req_params.sender,
+            req_params.salt.map(|salt| salt.to_be_bytes()),
+        )
+        .await
+        .map_err(Into::into),
+    )
+}
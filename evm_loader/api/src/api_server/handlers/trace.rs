@@ -8,7 +8,7 @@ use crate::{
     api_context, context::Context, types::request_models::TraceRequestModel, NeonApiState,
 };
 
-use super::{parse_emulation_params, process_result};
+use super::{check_chain_id_served, parse_emulation_params, process_result};
 
 #[tracing::instrument(skip(state, request_id), fields(id = request_id.as_str()))]
 #[post("/trace")]
@@ -17,6 +17,13 @@ pub async fn trace(
     request_id: RequestId,
     Json(trace_request): Json<TraceRequestModel>,
 ) -> impl Responder {
+    // A chain id given by the request is checked before any RPC round-trip
+    if let Some(chain_id) = trace_request.emulate_request.emulation_params.chain_id {
+        if let Err(e) = check_chain_id_served(&state.config.served_chain_ids, chain_id) {
+            return process_error(StatusCode::BAD_REQUEST, &e);
+        }
+    }
+
     let tx = trace_request.emulate_request.tx_params.into();
 
     let rpc_client =
@@ -34,6 +41,11 @@ pub async fn trace(
     )
     .await;
 
+    // Otherwise the chain id is read from the program parameters
+    if let Err(e) = check_chain_id_served(&state.config.served_chain_ids, chain) {
+        return process_error(StatusCode::BAD_REQUEST, &e);
+    }
+
     process_result(
         &trace_transaction(
             context.rpc_client,
@@ -46,6 +58,7 @@ pub async fn trace(
             &accounts,
             &solana_accounts,
             trace_request.trace_call_config.unwrap_or_default(),
+            (&state.config).into(),
         )
         .await
         .map_err(Into::into),
@@ -1,2 +1,3 @@
+pub mod emulation_cache;
 pub mod handlers;
 pub mod state;
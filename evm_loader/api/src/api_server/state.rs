@@ -1,3 +1,4 @@
+use crate::api_server::emulation_cache::EmulationCache;
 use crate::Config;
 use neon_lib::types::TracerDb;
 use solana_client::nonblocking::rpc_client::RpcClient;
@@ -7,6 +8,7 @@ pub struct State {
     pub tracer_db: TracerDb,
     pub rpc_client: Arc<RpcClient>,
     pub config: Config,
+    pub emulation_cache: EmulationCache,
 }
 
 impl State {
@@ -19,6 +21,7 @@ impl State {
                 config.commitment,
             )),
             config,
+            emulation_cache: EmulationCache::default(),
         }
     }
 }
@@ -6,6 +6,8 @@ mod api_server;
 #[allow(clippy::module_name_repetitions)]
 mod build_info;
 
+use actix_cors::Cors;
+use actix_web::http::header;
 use actix_web::web;
 use actix_web::App;
 use actix_web::HttpServer;
@@ -23,8 +25,13 @@ use std::{env, net::SocketAddr, str::FromStr};
 
 use crate::api_server::handlers::build_info::build_info_route;
 use crate::api_server::handlers::emulate::emulate;
+use crate::api_server::handlers::get_code::get_code;
 use crate::api_server::handlers::get_ether_account_data::get_ether_account_data;
 use crate::api_server::handlers::get_storage_at::get_storage_at;
+use crate::api_server::handlers::get_transaction_count::get_transaction_count;
+use crate::api_server::handlers::health::health;
+use crate::api_server::handlers::json_error_handler;
+use crate::api_server::handlers::prepare_deploy::prepare_deploy;
 use crate::api_server::handlers::trace::trace;
 use crate::build_info::get_build_info;
 pub use config::Config;
@@ -47,10 +54,18 @@ async fn main() -> NeonApiResult<()> {
 
     info!("{}", get_build_info());
 
-    let api_config = config::load_api_config_from_enviroment();
+    let api_config = config::load_api_config_from_enviroment()?;
 
     let config = config::create_from_api_config(&api_config)?;
 
+    let allowed_origins = api_config.allowed_origins;
+    let allowed_methods = if api_config.allowed_methods.is_empty() {
+        vec!["GET".to_owned(), "POST".to_owned()]
+    } else {
+        api_config.allowed_methods
+    };
+    let max_request_size = api_config.max_request_size;
+
     let state: NeonApiState = Data::new(api_server::state::State::new(config));
 
     let listener_addr = options
@@ -64,13 +79,34 @@ async fn main() -> NeonApiResult<()> {
     let addr = SocketAddr::from_str(listener_addr.as_str())?;
     tracing::info!("listening on {}", addr);
     HttpServer::new(move || {
-        App::new().service(
+        let cors = allowed_origins
+            .iter()
+            .fold(Cors::default(), |cors, origin| {
+                if origin == "*" {
+                    cors.allow_any_origin()
+                } else {
+                    cors.allowed_origin(origin)
+                }
+            })
+            .allowed_methods(allowed_methods.iter().map(String::as_str))
+            .allowed_header(header::CONTENT_TYPE);
+
+        let json_config = web::JsonConfig::default()
+            .limit(max_request_size)
+            .error_handler(json_error_handler);
+
+        App::new().wrap(cors).service(
             web::scope("/api")
                 .app_data(state.clone())
+                .app_data(json_config)
                 .service(build_info_route)
                 .service(emulate)
+                .service(get_code)
                 .service(get_ether_account_data)
                 .service(get_storage_at)
+                .service(get_transaction_count)
+                .service(health)
+                .service(prepare_deploy)
                 .service(trace)
                 .wrap(RequestIdentifier::with_uuid()),
         )
@@ -12,6 +12,7 @@ use std::str::FromStr;
 /// # Errors
 /// `EvmLoaderNotSpecified` - if `evm_loader` is not specified
 /// `KeypairNotSpecified` - if `signer` is not specified
+/// `InvalidEnvironmentValue` - if a list or a limit in the environment cannot be parsed
 pub fn create(options: &ArgMatches) -> Result<Config, NeonError> {
     let solana_cli_config = options
         .value_of("config_file")
@@ -57,5 +58,9 @@ pub fn create(options: &ArgMatches) -> Result<Config, NeonError> {
         db_config,
         json_rpc_url,
         keypair_path,
+        emulation_denylist: load_emulation_denylist_from_enviroment()?,
+        served_chain_ids: load_served_chain_ids_from_enviroment()?,
+        max_accounts_touched: load_max_accounts_touched_from_enviroment()?,
+        max_logs: load_max_logs_from_enviroment()?,
     })
 }
@@ -10,8 +10,8 @@ mod program_options;
 use neon_lib::{
     commands::{
         cancel_trx, collect_treasury, create_ether_account, deposit, emulate,
-        get_ether_account_data, get_neon_elf, get_neon_elf::CachedElfParams, get_storage_at,
-        init_environment, trace,
+        find_stale_transactions, get_ether_account_data, get_holder, get_neon_elf,
+        get_neon_elf::CachedElfParams, get_storage_at, init_environment, prepare_deploy, trace,
     },
     errors, rpc,
     types::{self, AccessListItem},
@@ -144,6 +144,7 @@ async fn execute<'a>(
                 &solana_accounts,
                 &trace_call_config.block_overrides,
                 trace_call_config.state_overrides,
+                config.into(),
             )
             .await
             .map(|result| json!(result))
@@ -163,6 +164,7 @@ async fn execute<'a>(
                 &accounts,
                 &solana_accounts,
                 trace_call_config,
+                config.into(),
             )
             .await
             .map(|trace| json!(trace))
@@ -207,6 +209,13 @@ async fn execute<'a>(
                 .await
                 .map(|result| json!(result))
         }
+        ("get-holder", Some(params)) => {
+            let holder_account =
+                pubkey_of(params, "holder_account").expect("holder_account parse error");
+            get_holder::execute(context.rpc_client, &config.evm_loader, &holder_account)
+                .await
+                .map(|result| json!(result))
+        }
         ("cancel-trx", Some(params)) => {
             let storage_account =
                 pubkey_of(params, "storage_account").expect("storage_account parse error");
@@ -219,6 +228,14 @@ async fn execute<'a>(
             .await
             .map(|result| json!(result))
         }
+        ("find-stale-transactions", Some(params)) => {
+            let threshold = value_of(params, "threshold")
+                .unwrap_or(find_stale_transactions::DEFAULT_STALE_THRESHOLD);
+            let cancel = params.is_present("cancel");
+            find_stale_transactions::execute(config, context, threshold, cancel)
+                .await
+                .map(|result| json!(result))
+        }
         ("neon-elf-params", Some(params)) => {
             let program_location = params.value_of("program_location");
             get_neon_elf::execute(config, context, program_location)
@@ -244,6 +261,27 @@ async fn execute<'a>(
                 .await
                 .map(|hash| json!(hex::encode(hash.0)))
         }
+        ("prepare-deploy", Some(params)) => {
+            let caller = address_of(params, "sender").expect("sender parse error");
+            let salt = u256_of(params, "salt").map(U256::to_be_bytes);
+            let transaction_params: Option<TransactionParams> = read_from_stdin()
+                .unwrap_or_else(|err| {
+                    panic!("Unable to parse `TransactionParams` from STDIN, error: {err:?}")
+                });
+            let init_code: Vec<u8> = transaction_params
+                .and_then(|params| params.data)
+                .map(Into::into)
+                .unwrap_or_default();
+            prepare_deploy::execute(
+                context.rpc_client,
+                &config.evm_loader,
+                &init_code,
+                &caller,
+                salt,
+            )
+            .await
+            .map(|result| json!(result))
+        }
         _ => unreachable!(),
     }
 }
@@ -315,6 +315,19 @@ pub fn parse<'a>() -> ArgMatches<'a> {
                 .about("Get values stored in associated with given address account data")
                 .arg(ether_arg(1))
         )
+        .subcommand(
+            SubCommand::with_name("get-holder")
+                .about("Decode the transaction staged in holder account")
+                .arg(
+                    Arg::with_name("holder_account")
+                        .index(1)
+                        .value_name("HOLDER_ACCOUNT")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(is_valid_pubkey)
+                        .help("holder account with transaction"),
+                )
+        )
         .subcommand(
             SubCommand::with_name("cancel-trx")
                 .about("Cancel NEON transaction")
@@ -328,6 +341,24 @@ pub fn parse<'a>() -> ArgMatches<'a> {
                         .help("storage account for transaction"),
                 )
         )
+        .subcommand(
+            SubCommand::with_name("find-stale-transactions")
+                .about("Find iterative transactions that are not finished for too long")
+                .arg(
+                    Arg::with_name("threshold")
+                        .long("threshold")
+                        .value_name("SLOTS")
+                        .takes_value(true)
+                        .validator(is_amount::<u64, _>)
+                        .help("Number of slots since the transaction start, 1000 by default"),
+                )
+                .arg(
+                    Arg::with_name("cancel")
+                        .long("cancel")
+                        .takes_value(false)
+                        .help("Cancel found transactions"),
+                )
+        )
         .subcommand(
             SubCommand::with_name("neon-elf-params")
                 .about("Get NEON values stored in elf")
@@ -394,5 +425,26 @@ pub fn parse<'a>() -> ArgMatches<'a> {
                         .required(true),
                 )
         )
+        .subcommand(
+            SubCommand::with_name("prepare-deploy")
+                .about("Derive the accounts of a contract deployment, init code is read from STDIN")
+                .arg(
+                    Arg::with_name("sender")
+                        .index(1)
+                        .value_name("SENDER")
+                        .takes_value(true)
+                        .validator(is_valid_address)
+                        .required(true)
+                        .help("The sender of the deployment"),
+                )
+                .arg(
+                    Arg::with_name("salt")
+                        .long("salt")
+                        .value_name("SALT")
+                        .takes_value(true)
+                        .validator(is_valid_u256)
+                        .help("CREATE2 salt, CREATE with the sender nonce is used if not set"),
+                )
+        )
         .get_matches()
 }
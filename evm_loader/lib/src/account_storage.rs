@@ -1,5 +1,10 @@
 use async_trait::async_trait;
-use std::{cell::RefCell, collections::HashMap, convert::TryInto, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    convert::TryInto,
+    rc::Rc,
+};
 
 use crate::{rpc::Rpc, NeonError};
 use ethnum::U256;
@@ -23,7 +28,7 @@ use solana_client::client_error;
 use solana_sdk::entrypoint::MAX_PERMITTED_DATA_INCREASE;
 use solana_sdk::{
     account::Account,
-    account_info::AccountInfo,
+    account_info::{AccountInfo, IntoAccountInfo},
     commitment_config::CommitmentConfig,
     pubkey,
     pubkey::Pubkey,
@@ -119,6 +124,8 @@ pub struct EmulatorAccountStorage<'a> {
     chain_id: u64,
     commitment: CommitmentConfig,
     state_overrides: Option<AccountOverrides>,
+    max_accounts_touched: Option<usize>,
+    accounts_limit_exceeded: Cell<bool>,
 }
 
 impl<'a> EmulatorAccountStorage<'a> {
@@ -160,6 +167,8 @@ impl<'a> EmulatorAccountStorage<'a> {
             chain_id,
             commitment,
             state_overrides,
+            max_accounts_touched: None,
+            accounts_limit_exceeded: Cell::new(false),
         })
     }
 
@@ -230,6 +239,24 @@ impl<'a> EmulatorAccountStorage<'a> {
         }
     }
 
+    /// Stop loading new accounts from Solana once `limit` accounts are touched
+    pub fn set_max_accounts_touched(&mut self, limit: Option<usize>) {
+        self.max_accounts_touched = limit;
+    }
+
+    fn accounts_limit_reached(&self) -> bool {
+        let Some(limit) = self.max_accounts_touched else {
+            return false;
+        };
+
+        let touched = self.accounts.borrow().len() + self.solana_accounts.borrow().len();
+        if touched >= limit {
+            self.accounts_limit_exceeded.set(true);
+        }
+
+        self.accounts_limit_exceeded.get()
+    }
+
     pub async fn get_account(&self, pubkey: &Pubkey) -> client_error::Result<Option<Account>> {
         if let Some(account) = self.solana_accounts.borrow().get(pubkey) {
             if let Some(ref data) = account.data {
@@ -237,6 +264,10 @@ impl<'a> EmulatorAccountStorage<'a> {
             }
         }
 
+        if !self.solana_accounts.borrow().contains_key(pubkey) && self.accounts_limit_reached() {
+            return Ok(None);
+        }
+
         let result = self
             .rpc_client
             .get_account_with_commitment(pubkey, self.commitment)
@@ -283,6 +314,14 @@ impl<'a> EmulatorAccountStorage<'a> {
             return true;
         }
 
+        if self.accounts_limit_reached() {
+            let (pubkey, _) = make_solana_program_address(address, &self.evm_loader);
+            let account = NeonAccount::new(*address, pubkey, None, writable);
+            self.accounts.borrow_mut().insert(*address, account);
+
+            return false;
+        }
+
         let account =
             NeonAccount::rpc_load(self.rpc_client, &self.evm_loader, *address, writable).await;
         self.accounts.borrow_mut().insert(*address, account);
@@ -299,6 +338,10 @@ impl<'a> EmulatorAccountStorage<'a> {
             return;
         }
 
+        if !self.solana_accounts.borrow().contains_key(&pubkey) && self.accounts_limit_reached() {
+            return;
+        }
+
         let mut solana_accounts = self.solana_accounts.borrow_mut();
 
         let account = SolanaAccount {
@@ -716,6 +759,11 @@ impl<'a> AccountStorage for EmulatorAccountStorage<'a> {
     where
         F: FnOnce(&AccountInfo) -> R,
     {
+        if address == &FAKE_OPERATOR {
+            let mut account = self.clone_solana_account(address).await;
+            return action(&(&mut account).into_account_info());
+        }
+
         self.add_solana_account(*address, false).await;
 
         let mut account = self
@@ -727,6 +775,10 @@ impl<'a> AccountStorage for EmulatorAccountStorage<'a> {
 
         action(&info)
     }
+
+    fn exceeded_accounts_limit(&self) -> Option<usize> {
+        self.max_accounts_touched.filter(|_| self.accounts_limit_exceeded.get())
+    }
 }
 
 /// Creates new instance of `AccountInfo` from `Account`.
@@ -749,3 +801,65 @@ pub fn make_solana_program_address(ether_address: &Address, program_id: &Pubkey)
         program_id,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_client::nonblocking::rpc_client::RpcClient;
+
+    fn storage(
+        rpc_client: &RpcClient,
+        max_accounts_touched: Option<usize>,
+    ) -> EmulatorAccountStorage {
+        EmulatorAccountStorage {
+            accounts: RefCell::default(),
+            solana_accounts: RefCell::default(),
+            rpc_client,
+            evm_loader: Pubkey::new_unique(),
+            block_number: 0,
+            block_timestamp: 0,
+            neon_token_mint: Pubkey::new_unique(),
+            chain_id: 111,
+            commitment: CommitmentConfig::confirmed(),
+            state_overrides: None,
+            max_accounts_touched,
+            accounts_limit_exceeded: Cell::new(false),
+        }
+    }
+
+    fn insert_account(storage: &EmulatorAccountStorage, address: Address) {
+        let account = NeonAccount::new(address, Pubkey::new_unique(), None, false);
+        storage.accounts.borrow_mut().insert(address, account);
+    }
+
+    #[tokio::test]
+    async fn accounts_over_the_limit_are_missing() {
+        let rpc_client = RpcClient::new_mock("succeeds".to_string());
+        let storage = storage(&rpc_client, Some(1));
+        insert_account(&storage, Address::from([0x11; 20]));
+        assert_eq!(storage.exceeded_accounts_limit(), None);
+
+        // Once the limit is reached accounts are not loaded from Solana,
+        // the error is reported by the caller after the execution
+        assert_eq!(storage.get_account(&Pubkey::new_unique()).await.unwrap(), None);
+        assert_eq!(storage.exceeded_accounts_limit(), Some(1));
+
+        let address = Address::from([0x22; 20]);
+        assert!(!storage.add_ethereum_account(&address, false).await);
+        assert!(storage.accounts.borrow()[&address].data.is_none());
+
+        storage.add_solana_account(Pubkey::new_unique(), false).await;
+        assert!(storage.solana_accounts.borrow().is_empty());
+    }
+
+    #[tokio::test]
+    async fn accounts_are_not_limited_by_default() {
+        let rpc_client = RpcClient::new_mock("succeeds".to_string());
+        let storage = storage(&rpc_client, None);
+        insert_account(&storage, Address::from([0x11; 20]));
+
+        storage.add_solana_account(Pubkey::new_unique(), false).await;
+        assert_eq!(storage.solana_accounts.borrow().len(), 1);
+        assert_eq!(storage.exceeded_accounts_limit(), None);
+    }
+}
@@ -1,4 +1,6 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
 
 use ethnum::U256;
 use log::{debug, info};
@@ -6,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
 
 use evm_loader::evm::tracing::TracerTypeOpt;
-use evm_loader::evm::tracing::{AccountOverrides, BlockOverrides};
+use evm_loader::evm::tracing::{AccountOverrides, AccountState, BlockOverrides, StateDiff};
 use evm_loader::{
     account_storage::AccountStorage,
     config::{EVM_STEPS_MIN, PAYMENT_TO_TREASURE},
@@ -19,12 +21,39 @@ use evm_loader::{
 use crate::types::TxParams;
 use crate::{
     account_storage::{EmulatorAccountStorage, NeonAccount, SolanaAccount},
+    commands::decode_revert_reason,
     errors::NeonError,
     rpc::Rpc,
     syscall_stubs::Stubs,
-    NeonResult,
+    Config, NeonResult,
 };
 
+/// Version of the `EmulationResultWithAccounts` layout, bumped when fields are added:
+/// - `1` added `response_version`, `tx_kind` and `created_address`
+/// - `2` added `revert_reason`
+pub const EMULATION_RESPONSE_VERSION: u32 = 2;
+
+/// Limits of a single emulation, nothing is limited by default
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EmulationLimits<'a> {
+    /// Contracts which must not be called by the transaction
+    pub denylist: &'a [Address],
+    /// Maximum number of distinct Solana accounts the emulation may touch
+    pub max_accounts_touched: Option<usize>,
+    /// Maximum number of events the transaction may emit, reverted calls are not counted
+    pub max_logs: Option<usize>,
+}
+
+impl<'a> From<&'a Config> for EmulationLimits<'a> {
+    fn from(config: &'a Config) -> Self {
+        Self {
+            denylist: &config.emulation_denylist,
+            max_accounts_touched: config.max_accounts_touched,
+            max_logs: config.max_logs,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmulationResult {
     #[serde(serialize_with = "serde_hex_serialize")]
@@ -34,6 +63,9 @@ pub struct EmulationResult {
     pub steps_executed: u64,
     pub used_gas: u64,
     pub actions: Vec<Action>,
+    /// Decoded reason of a reverted transaction
+    #[serde(default)]
+    pub revert_reason: Option<String>,
 }
 
 impl Display for EmulationResult {
@@ -52,21 +84,63 @@ impl Display for EmulationResult {
 
 impl From<evm_loader::evm::tracing::EmulationResult> for EmulationResult {
     fn from(value: evm_loader::evm::tracing::EmulationResult) -> Self {
+        let revert_reason = match &value.exit_status {
+            ExitStatus::Revert(data) => decode_revert_reason(data),
+            _ => None,
+        };
+
         Self {
             exit_status: value.exit_status.status().to_string(),
             result: value.exit_status.into_result().unwrap_or_default(),
             steps_executed: value.steps_executed,
             used_gas: value.used_gas,
             actions: value.actions,
+            revert_reason,
+        }
+    }
+}
+
+/// Kind of the emulated transaction, as seen before its execution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TxKind {
+    /// Call without data to an account without code
+    ValueTransfer,
+    /// Call to an account with code
+    ContractCall,
+    /// Transaction without a target
+    ContractCreation,
+    /// Call with data to an account without code
+    EmptyAccountCall,
+}
+
+impl TxKind {
+    #[must_use]
+    pub fn new(target_has_code: Option<bool>, has_data: bool) -> Self {
+        match target_has_code {
+            None => Self::ContractCreation,
+            Some(true) => Self::ContractCall,
+            Some(false) if has_data => Self::EmptyAccountCall,
+            Some(false) => Self::ValueTransfer,
         }
     }
 }
 
+/// Fields are only ever added to the response and every added field has a default,
+/// so a response of an older version deserializes into the current one.
+/// `response_version` is `0` for responses produced before it was introduced.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmulationResultWithAccounts {
+    #[serde(default)]
+    pub response_version: u32,
     pub accounts: Vec<NeonAccount>,
     pub solana_accounts: Vec<SolanaAccount>,
     pub token_accounts: Vec<SolanaAccount>,
+    #[serde(default)]
+    pub tx_kind: Option<TxKind>,
+    /// Address of the deployed contract, if the transaction is a contract creation
+    #[serde(default)]
+    pub created_address: Option<Address>,
     #[serde(flatten)]
     pub emulation_result: EmulationResult,
 }
@@ -122,7 +196,12 @@ pub async fn execute(
     solana_accounts: &[Pubkey],
     block_overrides: &Option<BlockOverrides>,
     state_overrides: Option<AccountOverrides>,
+    limits: EmulationLimits<'_>,
 ) -> NeonResult<EmulationResultWithAccounts> {
+    let target = tx_params.to;
+    let (from, nonce) = (tx_params.from, tx_params.nonce);
+    let has_data = tx_params.data.as_ref().map_or(false, |data| !data.is_empty());
+
     let (emulation_result, storage) = emulate_transaction(
         rpc_client,
         evm_loader,
@@ -136,15 +215,39 @@ pub async fn execute(
         block_overrides,
         state_overrides,
         None,
+        None,
+        false,
+        limits,
     )
     .await?;
+
+    // Emulation does not modify the storage, so this is the code before the transaction
+    let target_has_code = match target {
+        Some(target) => Some(storage.code_size(&target).await > 0),
+        None => None,
+    };
+    let tx_kind = TxKind::new(target_has_code, has_data);
+
+    let created_address = if target.is_none() {
+        let nonce = match nonce {
+            Some(nonce) => nonce,
+            None => storage.nonce(&from).await,
+        };
+        Some(Address::from_create(&from, nonce))
+    } else {
+        None
+    };
+
     let accounts = storage.accounts.borrow().values().cloned().collect();
     let solana_accounts = storage.solana_accounts.borrow().values().cloned().collect();
 
     Ok(EmulationResultWithAccounts {
+        response_version: EMULATION_RESPONSE_VERSION,
         accounts,
         solana_accounts,
         token_accounts: vec![],
+        tx_kind: Some(tx_kind),
+        created_address,
         emulation_result: emulation_result.into(),
     })
 }
@@ -163,6 +266,9 @@ pub(crate) async fn emulate_transaction<'a>(
     block_overrides: &Option<BlockOverrides>,
     state_overrides: Option<AccountOverrides>,
     tracer: TracerTypeOpt,
+    timeout: Option<Duration>,
+    state_diff: bool,
+    limits: EmulationLimits<'_>,
 ) -> Result<
     (
         evm_loader::evm::tracing::EmulationResult,
@@ -172,7 +278,7 @@ pub(crate) async fn emulate_transaction<'a>(
 > {
     setup_syscall_stubs(rpc_client).await?;
 
-    let storage = EmulatorAccountStorage::with_accounts(
+    let mut storage = EmulatorAccountStorage::with_accounts(
         rpc_client,
         evm_loader,
         token_mint,
@@ -184,19 +290,37 @@ pub(crate) async fn emulate_transaction<'a>(
         state_overrides,
     )
     .await?;
+    storage.set_max_accounts_touched(limits.max_accounts_touched);
 
-    emulate_trx(tx_params, &storage, chain_id, step_limit, tracer)
-        .await
-        .map(move |result| (result, storage))
+    emulate_trx(
+        tx_params,
+        &storage,
+        chain_id,
+        step_limit,
+        tracer,
+        timeout,
+        state_diff,
+        limits,
+    )
+    .await
+    .map(move |result| (result, storage))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn emulate_trx<'a>(
     tx_params: TxParams,
     storage: &'a EmulatorAccountStorage<'a>,
     chain_id: u64,
     step_limit: u64,
     tracer: TracerTypeOpt,
+    timeout: Option<Duration>,
+    state_diff: bool,
+    limits: EmulationLimits<'_>,
 ) -> Result<evm_loader::evm::tracing::EmulationResult, NeonError> {
+    if let Some(to) = tx_params.to.filter(|to| limits.denylist.contains(to)) {
+        return Err(NeonError::AddressDenied(to));
+    }
+
     let (exit_status, actions, steps_executed) = {
         let mut backend = ExecutorState::new(storage);
         let trx_payload = if tx_params.access_list.is_some() {
@@ -260,9 +384,32 @@ pub(crate) async fn emulate_trx<'a>(
         };
 
         let mut evm = Machine::new(&mut trx, tx_params.from, &mut backend, tracer).await?;
+        evm.set_denylist(limits.denylist);
+        if let Some(max_logs) = limits.max_logs {
+            evm.set_logs_limit(max_logs);
+        }
+        if let Some(timeout) = timeout {
+            evm.set_deadline(Instant::now() + timeout);
+        }
 
-        let (result, steps_executed) = evm.execute(step_limit, &mut backend).await?;
-        if result == ExitStatus::StepLimit {
+        let (result, steps_executed) = match evm.execute(step_limit, &mut backend).await {
+            Err(evm_loader::error::Error::TooManyLogs(contract)) => {
+                return Err(NeonError::TooManyLogs(contract))
+            }
+            Err(evm_loader::error::Error::AddressDenied(contract)) => {
+                return Err(NeonError::AddressDenied(contract))
+            }
+            Err(evm_loader::error::Error::TooManyAccountsTouched(limit)) => {
+                return Err(NeonError::TooManyAccountsTouched(limit))
+            }
+            result => result?,
+        };
+        // The limit may be reached before the first opcode, e.g. by loading the origin
+        if let Some(limit) = storage.exceeded_accounts_limit() {
+            return Err(NeonError::TooManyAccountsTouched(limit));
+        }
+        // A timed out execution is returned as is, so the trace collected so far is kept
+        if result == ExitStatus::StepLimit && !evm.is_timed_out() {
             return Err(NeonError::TooManySteps);
         }
 
@@ -273,6 +420,12 @@ pub(crate) async fn emulate_trx<'a>(
     debug!("Execute done, result={exit_status:?}");
     debug!("{steps_executed} steps executed");
 
+    let state_diff = if state_diff {
+        Some(collect_state_diff(storage, &actions).await)
+    } else {
+        None
+    };
+
     let accounts_operations = storage.calc_accounts_operations(&actions).await;
 
     let max_iterations = (steps_executed + (EVM_STEPS_MIN - 1)) / EVM_STEPS_MIN;
@@ -287,12 +440,134 @@ pub(crate) async fn emulate_trx<'a>(
         steps_executed,
         used_gas: steps_gas + begin_end_gas + actions_gas + accounts_gas,
         actions,
+        state_diff,
     })
 }
 
+/// Reads the state of the touched accounts and applies `actions` on top of it
+async fn collect_state_diff(storage: &EmulatorAccountStorage<'_>, actions: &[Action]) -> StateDiff {
+    let mut addresses: BTreeSet<Address> = storage.accounts.borrow().keys().copied().collect();
+    let mut slots: BTreeMap<Address, BTreeSet<U256>> = BTreeMap::new();
+
+    for action in actions {
+        match action {
+            Action::NeonTransfer { source, target, .. } => {
+                addresses.insert(*source);
+                addresses.insert(*target);
+            }
+            Action::NeonWithdraw { source: address, .. }
+            | Action::EvmIncrementNonce { address }
+            | Action::EvmSetCode { address, .. }
+            | Action::EvmSelfDestruct { address } => {
+                addresses.insert(*address);
+            }
+            Action::EvmSetStorage { address, index, .. } => {
+                addresses.insert(*address);
+                slots.entry(*address).or_default().insert(*index);
+            }
+            Action::ExternalInstruction { .. } => {}
+        }
+    }
+
+    let mut pre = BTreeMap::new();
+    for address in addresses {
+        let mut state = AccountState {
+            balance: storage.balance(&address).await,
+            nonce: storage.nonce(&address).await,
+            code: storage.code(&address).await.to_vec(),
+            storage: BTreeMap::new(),
+        };
+
+        for index in slots.get(&address).into_iter().flatten() {
+            let value = storage.storage(&address, index).await;
+            state.storage.insert(*index, value);
+        }
+
+        pre.insert(address, state);
+    }
+
+    let mut post = pre.clone();
+    for action in actions {
+        match action {
+            Action::NeonTransfer {
+                source,
+                target,
+                value,
+            } => {
+                let source = post.entry(*source).or_default();
+                source.balance = source.balance.saturating_sub(*value);
+
+                let target = post.entry(*target).or_default();
+                target.balance = target.balance.saturating_add(*value);
+            }
+            Action::NeonWithdraw { source, value } => {
+                let source = post.entry(*source).or_default();
+                source.balance = source.balance.saturating_sub(*value);
+            }
+            Action::EvmSetStorage {
+                address,
+                index,
+                value,
+            } => {
+                post.entry(*address).or_default().storage.insert(*index, *value);
+            }
+            Action::EvmIncrementNonce { address } => {
+                let account = post.entry(*address).or_default();
+                account.nonce = account.nonce.saturating_add(1);
+            }
+            Action::EvmSetCode { address, code } => {
+                post.entry(*address).or_default().code = code.to_vec();
+            }
+            Action::EvmSelfDestruct { address } => {
+                post.remove(address);
+            }
+            Action::ExternalInstruction { .. } => {}
+        }
+    }
+
+    StateDiff { pre, post }
+}
+
 pub(crate) async fn setup_syscall_stubs(rpc_client: &dyn Rpc) -> Result<(), NeonError> {
     let syscall_stubs = Stubs::new(rpc_client).await?;
     solana_sdk::program_stubs::set_syscall_stubs(syscall_stubs);
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tx_kind() {
+        assert_eq!(TxKind::new(None, true), TxKind::ContractCreation);
+        assert_eq!(TxKind::new(None, false), TxKind::ContractCreation);
+        assert_eq!(TxKind::new(Some(true), true), TxKind::ContractCall);
+        assert_eq!(TxKind::new(Some(true), false), TxKind::ContractCall);
+        assert_eq!(TxKind::new(Some(false), false), TxKind::ValueTransfer);
+        assert_eq!(TxKind::new(Some(false), true), TxKind::EmptyAccountCall);
+    }
+
+    #[test]
+    fn deserialize_unversioned_response() {
+        let response = r#"{
+            "accounts": [],
+            "solana_accounts": [],
+            "token_accounts": [],
+            "result": "",
+            "exit_status": "succeed",
+            "steps_executed": 1,
+            "used_gas": 5000,
+            "actions": []
+        }"#;
+
+        let response: EmulationResultWithAccounts = serde_json::from_str(response).unwrap();
+
+        assert_eq!(response.response_version, 0);
+        assert_eq!(response.tx_kind, None);
+        assert_eq!(response.created_address, None);
+        assert_eq!(response.emulation_result.exit_status, "succeed");
+        assert_eq!(response.emulation_result.revert_reason, None);
+    }
+}
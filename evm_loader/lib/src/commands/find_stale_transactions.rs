@@ -0,0 +1,163 @@
+use evm_loader::{account::State, types::Address};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    nonblocking::rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_sdk::{clock::Slot, signature::Signature};
+
+use crate::{
+    account_storage::account_info,
+    commands::{cancel_trx, get_transaction_count},
+    Config, Context, NeonResult,
+};
+
+/// Iterative transactions started this many slots ago are considered stale by default
+pub const DEFAULT_STALE_THRESHOLD: Slot = 1000;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StaleTransaction {
+    pub storage_account: String,
+    pub transaction_hash: String,
+    pub caller: Address,
+    /// Current nonce of the caller account
+    pub nonce: u64,
+    pub operator: String,
+    /// Slot the current operator started the transaction at
+    pub slot: Slot,
+    /// Cancel transaction, if cancelling was requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cancel_transaction: Option<Signature>,
+    /// Why cancelling failed, the other stale transactions are still cancelled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cancel_error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FindStaleTransactionsReturn {
+    pub current_slot: Slot,
+    pub transactions: Vec<StaleTransaction>,
+}
+
+pub async fn execute(
+    config: &Config,
+    context: &Context<'_>,
+    threshold: Slot,
+    cancel: bool,
+) -> NeonResult<FindStaleTransactionsReturn> {
+    let client = context
+        .rpc_client
+        .as_any()
+        .downcast_ref::<RpcClient>()
+        .expect("cast to solana_client::rpc_client::RpcClient error");
+
+    let current_slot = context.rpc_client.get_slot().await?;
+
+    let accounts = client
+        .get_program_accounts_with_config(
+            &config.evm_loader,
+            RpcProgramAccountsConfig {
+                filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                    0,
+                    vec![State::TAG],
+                ))]),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    commitment: Some(config.commitment),
+                    ..RpcAccountInfoConfig::default()
+                },
+                ..RpcProgramAccountsConfig::default()
+            },
+        )
+        .await?;
+
+    let signer = if cancel {
+        Some(context.signer()?)
+    } else {
+        None
+    };
+
+    let mut transactions = vec![];
+    for (storage_account, mut account) in accounts {
+        let (transaction_hash, caller, operator, slot) = {
+            let storage_info = account_info(&storage_account, &mut account);
+            let storage = State::from_account(&config.evm_loader, &storage_info)?;
+            (
+                storage.transaction_hash,
+                storage.caller,
+                storage.operator,
+                storage.slot,
+            )
+        };
+
+        if !is_stale(current_slot, slot, threshold) {
+            continue;
+        }
+
+        info!("Stale transaction {} in {}", hex::encode(transaction_hash), storage_account);
+
+        let nonce =
+            get_transaction_count::execute(context.rpc_client, &config.evm_loader, &caller)
+                .await?
+                .nonce;
+
+        let (cancel_transaction, cancel_error) = if let Some(signer) = &signer {
+            let result = cancel_trx::execute(
+                context.rpc_client,
+                signer.as_ref(),
+                config.evm_loader,
+                &storage_account,
+            )
+            .await;
+
+            match result {
+                Ok(result) => (Some(result.transaction), None),
+                Err(e) => {
+                    warn!("Failed to cancel the transaction in {storage_account}: {e}");
+                    (None, Some(e.to_string()))
+                }
+            }
+        } else {
+            (None, None)
+        };
+
+        transactions.push(StaleTransaction {
+            storage_account: storage_account.to_string(),
+            transaction_hash: hex::encode(transaction_hash),
+            caller,
+            nonce,
+            operator: operator.to_string(),
+            slot,
+            cancel_transaction,
+            cancel_error,
+        });
+    }
+
+    Ok(FindStaleTransactionsReturn {
+        current_slot,
+        transactions,
+    })
+}
+
+/// A transaction is stale once more than `threshold` slots passed since it was started
+fn is_stale(current_slot: Slot, slot: Slot, threshold: Slot) -> bool {
+    current_slot.saturating_sub(slot) > threshold
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stale_threshold() {
+        assert!(!is_stale(2000, 1000, DEFAULT_STALE_THRESHOLD));
+        assert!(is_stale(2001, 1000, DEFAULT_STALE_THRESHOLD));
+        assert!(!is_stale(1000, 1000, 0));
+        assert!(is_stale(1001, 1000, 0));
+        // Transactions started after the slot was read are not stale
+        assert!(!is_stale(1000, 1001, 0));
+    }
+}
@@ -0,0 +1,33 @@
+use std::fmt::{Display, Formatter};
+
+use evm_loader::types::{hexbytes::HexBytes, Address};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{commands::read_ether_account, rpc::Rpc, NeonResult};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct GetCodeReturn(pub HexBytes);
+
+impl Display for GetCodeReturn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{}", hex::encode(&*self.0))
+    }
+}
+
+/// Code deployed at `ether_address`, empty for accounts without code
+pub async fn execute(
+    rpc_client: &dyn Rpc,
+    evm_loader: &Pubkey,
+    ether_address: &Address,
+) -> NeonResult<GetCodeReturn> {
+    let code = read_ether_account(rpc_client, evm_loader, ether_address, |account| {
+        account
+            .contract_data()
+            .map_or_else(Vec::new, |contract| contract.code().to_vec())
+    })
+    .await?
+    .unwrap_or_default();
+
+    Ok(GetCodeReturn(code.into()))
+}
@@ -0,0 +1,113 @@
+use evm_loader::{
+    account::Holder,
+    types::{Address, Transaction},
+};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::fmt::{Display, Formatter};
+
+use crate::{account_storage::account_info, rpc::Rpc, NeonResult};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HolderTransaction {
+    pub hash: String,
+    pub from: Address,
+    pub to: Option<Address>,
+    pub nonce: u64,
+    pub value: String,
+    pub gas_price: String,
+    pub gas_limit: String,
+    pub chain_id: Option<String>,
+    pub call_data: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetHolderReturn {
+    pub owner: String,
+    pub transaction_hash: String,
+    /// Number of transaction bytes written to the holder so far
+    pub written_len: usize,
+    /// Transaction length declared by its RLP header, if the header is already written
+    pub expected_len: Option<usize>,
+    /// Staged transaction, if it is completely written and valid
+    pub transaction: Option<HolderTransaction>,
+}
+
+impl Display for GetHolderReturn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let expected_len = self
+            .expected_len
+            .map_or_else(|| "unknown".to_string(), |len| len.to_string());
+
+        write!(
+            f,
+            "{{ owner: {}, transaction_hash: {}, written: {}/{} bytes",
+            self.owner, self.transaction_hash, self.written_len, expected_len,
+        )?;
+
+        if let Some(trx) = &self.transaction {
+            let to = trx.to.map_or_else(|| "deploy".to_string(), |to| to.to_string());
+            write!(
+                f,
+                ", from: {}, to: {}, nonce: {}, value: {}, call_data: {}",
+                trx.from, to, trx.nonce, trx.value, trx.call_data,
+            )?;
+        }
+
+        write!(f, " }}")
+    }
+}
+
+pub async fn execute(
+    rpc_client: &dyn Rpc,
+    evm_loader: &Pubkey,
+    holder_account: &Pubkey,
+) -> NeonResult<GetHolderReturn> {
+    let mut acc = rpc_client.get_account(holder_account).await?;
+    let holder_info = account_info(holder_account, &mut acc);
+    let holder = Holder::from_account(evm_loader, &holder_info)?;
+
+    // Decoded the same way as in the TransactionStepFromAccount instruction
+    let message = holder.transaction();
+    let transaction = Transaction::from_rlp(&message)
+        .ok()
+        .and_then(|trx| decode_transaction(&trx));
+
+    Ok(GetHolderReturn {
+        owner: holder.owner.to_string(),
+        transaction_hash: hex::encode(holder.transaction_hash),
+        written_len: holder.transaction_len,
+        expected_len: expected_len(&message),
+        transaction,
+    })
+}
+
+fn decode_transaction(trx: &Transaction) -> Option<HolderTransaction> {
+    let from = trx.recover_caller_address().ok()?;
+
+    Some(HolderTransaction {
+        hash: hex::encode(trx.hash()),
+        from,
+        to: trx.target(),
+        nonce: trx.nonce(),
+        value: trx.value().to_string(),
+        gas_price: trx.gas_price().to_string(),
+        gas_limit: trx.gas_limit().to_string(),
+        chain_id: trx.chain_id().map(|chain_id| chain_id.to_string()),
+        call_data: hex::encode(&trx.call_data()[..]),
+    })
+}
+
+/// Length of the transaction according to its RLP header
+/// and the EIP-2718 type byte, if any
+fn expected_len(transaction: &[u8]) -> Option<usize> {
+    let (type_len, payload) = match transaction.first()? {
+        0x00..=0x02 => (1, &transaction[1..]),
+        0xc0..=0xff => (0, transaction),
+        _ => return None,
+    };
+
+    // The header alone is enough, the payload may not be written yet
+    let payload_info = rlp::PayloadInfo::from(payload).ok()?;
+    Some(type_len + payload_info.total())
+}
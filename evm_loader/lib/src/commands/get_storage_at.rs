@@ -3,6 +3,7 @@ use std::fmt::{Display, Formatter};
 
 use ethnum::U256;
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 use solana_sdk::pubkey::Pubkey;
 
 use evm_loader::account::EthereumAccount;
@@ -14,6 +15,7 @@ use evm_loader::{
 
 use crate::{
     account_storage::{account_info, EmulatorAccountStorage},
+    errors::NeonError,
     rpc::Rpc,
     NeonResult,
 };
@@ -27,6 +29,45 @@ impl Display for GetStorageAtReturn {
     }
 }
 
+/// Representation of the storage value in the response
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageValueEncoding {
+    /// 0x prefixed 32 bytes hex string
+    #[default]
+    Hex,
+    /// Decimal string of the uint256 value
+    Uint,
+    /// Address stored in the low 20 bytes, the high 12 bytes must be zero
+    Address,
+    /// Array of 32 bytes
+    Bytes,
+}
+
+impl GetStorageAtReturn {
+    /// # Errors
+    /// `StorageValueNotAddress` if the value does not fit an address
+    pub fn encode(&self, encoding: StorageValueEncoding) -> NeonResult<Value> {
+        let value = match encoding {
+            StorageValueEncoding::Hex => json!(self.to_string()),
+            StorageValueEncoding::Uint => json!(U256::from_be_bytes(self.0).to_string()),
+            StorageValueEncoding::Address => {
+                let (high, low) = self.0.split_at(12);
+                if high.iter().any(|&byte| byte != 0) {
+                    return Err(NeonError::StorageValueNotAddress(self.to_string()));
+                }
+
+                let mut address = [0_u8; 20];
+                address.copy_from_slice(low);
+                json!(Address(address).to_string())
+            }
+            StorageValueEncoding::Bytes => json!(self.0),
+        };
+
+        Ok(value)
+    }
+}
+
 pub async fn execute(
     rpc_client: &dyn Rpc,
     evm_loader: &Pubkey,
@@ -79,3 +120,59 @@ pub async fn execute(
 
     Ok(value)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encoding_round_trip() {
+        let mut value = [0_u8; 32];
+        value[12..].copy_from_slice(&[0xab; 20]);
+        value[31] = 0x01;
+        let storage = GetStorageAtReturn(value);
+
+        let hex = storage.encode(StorageValueEncoding::Hex).unwrap();
+        let hex = hex.as_str().unwrap().strip_prefix("0x").unwrap();
+        assert_eq!(hex::decode(hex).unwrap(), value);
+
+        let uint = storage.encode(StorageValueEncoding::Uint).unwrap();
+        let uint: U256 = uint.as_str().unwrap().parse().unwrap();
+        assert_eq!(uint.to_be_bytes(), value);
+
+        let address = storage.encode(StorageValueEncoding::Address).unwrap();
+        let address = Address::from_hex(address.as_str().unwrap()).unwrap();
+        assert_eq!(address.as_bytes(), &value[12..]);
+
+        let bytes = storage.encode(StorageValueEncoding::Bytes).unwrap();
+        let bytes: [u8; 32] = serde_json::from_value(bytes).unwrap();
+        assert_eq!(bytes, value);
+    }
+
+    #[test]
+    fn address_encoding_with_dirty_high_bytes() {
+        let mut value = [0_u8; 32];
+        value[12..].copy_from_slice(&[0xab; 20]);
+        value[0] = 0x01;
+        let storage = GetStorageAtReturn(value);
+
+        assert!(matches!(
+            storage.encode(StorageValueEncoding::Address),
+            Err(NeonError::StorageValueNotAddress(hex)) if hex == storage.to_string()
+        ));
+        assert!(storage.encode(StorageValueEncoding::Uint).is_ok());
+    }
+
+    #[test]
+    fn encoding_names() {
+        for (name, encoding) in [
+            ("hex", StorageValueEncoding::Hex),
+            ("uint", StorageValueEncoding::Uint),
+            ("address", StorageValueEncoding::Address),
+            ("bytes", StorageValueEncoding::Bytes),
+        ] {
+            let parsed: StorageValueEncoding = serde_json::from_value(json!(name)).unwrap();
+            assert_eq!(json!(parsed), json!(encoding));
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use evm_loader::types::Address;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{commands::read_ether_account, rpc::Rpc, NeonResult};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct GetTransactionCountResponse {
+    pub nonce: u64,
+}
+
+/// Nonce of `ether_address`, zero for accounts that do not exist yet
+pub async fn execute(
+    rpc_client: &dyn Rpc,
+    evm_loader: &Pubkey,
+    ether_address: &Address,
+) -> NeonResult<GetTransactionCountResponse> {
+    let nonce =
+        read_ether_account(rpc_client, evm_loader, ether_address, |account| account.trx_count)
+            .await?
+            .unwrap_or_default();
+
+    Ok(GetTransactionCountResponse { nonce })
+}
@@ -1,4 +1,13 @@
-use crate::rpc::Rpc;
+use crate::{
+    account_storage::{account_info, EmulatorAccountStorage},
+    rpc::Rpc,
+    NeonResult,
+};
+use evm_loader::{
+    account::EthereumAccount,
+    error::{format_revert_error, format_revert_panic},
+    types::Address,
+};
 use solana_client::{
     client_error::Result as SolanaClientResult, rpc_config::RpcSendTransactionConfig,
 };
@@ -6,6 +15,7 @@ use solana_sdk::{
     commitment_config::{CommitmentConfig, CommitmentLevel},
     instruction::Instruction,
     message::Message,
+    pubkey::Pubkey,
     signature::Signature,
     signer::Signer,
     transaction::Transaction,
@@ -16,10 +26,15 @@ pub mod collect_treasury;
 pub mod create_ether_account;
 pub mod deposit;
 pub mod emulate;
+pub mod find_stale_transactions;
+pub mod get_code;
 pub mod get_ether_account_data;
+pub mod get_holder;
 pub mod get_neon_elf;
 pub mod get_storage_at;
+pub mod get_transaction_count;
 pub mod init_environment;
+pub mod prepare_deploy;
 pub mod trace;
 mod transaction_executor;
 
@@ -47,3 +62,86 @@ pub async fn send_transaction(
         )
         .await
 }
+
+/// Loads the Ethereum account of `ether_address` and reads it with `read`,
+/// `None` if the account does not exist
+pub(crate) async fn read_ether_account<R>(
+    rpc_client: &dyn Rpc,
+    evm_loader: &Pubkey,
+    ether_address: &Address,
+    read: impl FnOnce(&EthereumAccount) -> R,
+) -> NeonResult<Option<R>> {
+    let (solana_address, Some(mut account)) =
+        EmulatorAccountStorage::get_account_from_solana(rpc_client, evm_loader, ether_address)
+            .await
+    else {
+        return Ok(None);
+    };
+
+    let info = account_info(&solana_address, &mut account);
+    let account_data = EthereumAccount::from_account(evm_loader, &info)?;
+
+    Ok(Some(read(&account_data)))
+}
+
+/// Decodes the reason of a reverted transaction from the standard
+/// `Error(string)` and `Panic(uint256)` payloads, `None` for anything else
+#[must_use]
+pub fn decode_revert_reason(data: &[u8]) -> Option<String> {
+    if let Some(reason) = format_revert_error(data) {
+        return Some(reason.to_string());
+    }
+
+    format_revert_panic(data).map(|code| format!("Panic({code:#x})"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn revert_error(reason: &str) -> Vec<u8> {
+        let mut data = vec![0x08, 0xc3, 0x79, 0xa0];
+        data.extend_from_slice(&[0; 31]);
+        data.push(0x20);
+        data.extend_from_slice(&[0; 24]);
+        data.extend_from_slice(&u64::try_from(reason.len()).unwrap().to_be_bytes());
+        data.extend_from_slice(reason.as_bytes());
+        data
+    }
+
+    #[test]
+    fn decode_error_string() {
+        let data = revert_error("Not enough balance");
+
+        assert_eq!(decode_revert_reason(&data), Some("Not enough balance".to_string()));
+    }
+
+    #[test]
+    fn decode_panic_code() {
+        let mut data = vec![0x4e, 0x48, 0x7b, 0x71];
+        data.extend_from_slice(&[0; 31]);
+        data.push(0x11);
+
+        assert_eq!(decode_revert_reason(&data), Some("Panic(0x11)".to_string()));
+    }
+
+    #[test]
+    fn decode_empty_data() {
+        assert_eq!(decode_revert_reason(&[]), None);
+    }
+
+    #[test]
+    fn decode_malformed_data() {
+        let data = revert_error("Not enough balance");
+
+        for length in 0..data.len() {
+            assert_eq!(decode_revert_reason(&data[..length]), None);
+        }
+        assert_eq!(decode_revert_reason(&[0x4e, 0x48, 0x7b, 0x71, 0x11]), None);
+        assert_eq!(decode_revert_reason(&[0xde, 0xad, 0xbe, 0xef]), None);
+
+        let mut data = revert_error("");
+        data[4 + 63] = 0xff;
+        assert_eq!(decode_revert_reason(&data), None);
+    }
+}
@@ -0,0 +1,88 @@
+use evm_loader::types::Address;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{commands::get_transaction_count, rpc::Rpc, NeonResult};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PrepareDeployReturn {
+    pub contract_address: Address,
+    pub contract_account: String,
+    pub required_accounts: Vec<String>,
+}
+
+/// Derives the accounts of a contract deployment without running the constructor.
+/// The contract address is `CREATE2(caller, salt, init_code)` if `salt` is set,
+/// otherwise `CREATE(caller, nonce)` with the current nonce of the caller.
+pub async fn execute(
+    rpc_client: &dyn Rpc,
+    evm_loader: &Pubkey,
+    init_code: &[u8],
+    caller: &Address,
+    salt: Option<[u8; 32]>,
+) -> NeonResult<PrepareDeployReturn> {
+    let contract_address = if let Some(salt) = salt {
+        Address::from_create2(caller, &salt, init_code)
+    } else {
+        let count = get_transaction_count::execute(rpc_client, evm_loader, caller).await?;
+        Address::from_create(caller, count.nonce)
+    };
+
+    Ok(derive_accounts(evm_loader, caller, contract_address))
+}
+
+/// Derives the program accounts of the caller and the contract,
+/// the same accounts the program creates when the deployment is finalized.
+fn derive_accounts(
+    evm_loader: &Pubkey,
+    caller: &Address,
+    contract_address: Address,
+) -> PrepareDeployReturn {
+    let (caller_account, _) = caller.find_solana_address(evm_loader);
+    let (contract_account, _) = contract_address.find_solana_address(evm_loader);
+
+    PrepareDeployReturn {
+        contract_address,
+        contract_account: contract_account.to_string(),
+        required_accounts: vec![caller_account.to_string(), contract_account.to_string()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use evm_loader::account::ACCOUNT_SEED_VERSION;
+
+    #[test]
+    fn derived_accounts_match_program_accounts() {
+        let evm_loader = Pubkey::new_unique();
+        let caller = Address::from([0x11; 20]);
+        let contract_address = Address::from_create(&caller, 0);
+
+        let result = derive_accounts(&evm_loader, &caller, contract_address);
+
+        let seeds: &[&[u8]] = &[&[ACCOUNT_SEED_VERSION], contract_address.as_bytes()];
+        let (contract_account, _) = Pubkey::find_program_address(seeds, &evm_loader);
+        let seeds: &[&[u8]] = &[&[ACCOUNT_SEED_VERSION], caller.as_bytes()];
+        let (caller_account, _) = Pubkey::find_program_address(seeds, &evm_loader);
+
+        assert_eq!(result.contract_address, contract_address);
+        assert_eq!(result.contract_account, contract_account.to_string());
+        assert_eq!(
+            result.required_accounts,
+            vec![caller_account.to_string(), contract_account.to_string()]
+        );
+    }
+
+    #[test]
+    fn create2_address() {
+        // EIP-1014, example 0
+        let caller = Address::default();
+        let contract_address = Address::from_create2(&caller, &[0; 32], &[0x00]);
+
+        assert_eq!(
+            contract_address,
+            Address::from_hex("4d1a2e2bb4f88f0250f26ffff098b0b30b26bf38").unwrap()
+        );
+    }
+}
@@ -1,17 +1,22 @@
 use std::fmt::{Display, Formatter};
 use std::rc::Rc;
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
 
-use evm_loader::evm::tracing::tracers::new_tracer;
-use evm_loader::evm::tracing::{TraceCallConfig, TraceConfig};
+use evm_loader::evm::tracing::tracers::{needs_state_diff, new_tracer};
+use evm_loader::evm::tracing::{EmulationResult, TraceCallConfig, TraceConfig, TracerType};
+use evm_loader::evm::ExitStatus;
 use evm_loader::types::Address;
 
 use crate::{
     account_storage::EmulatorAccountStorage,
-    commands::emulate::{emulate_transaction, emulate_trx, setup_syscall_stubs},
+    commands::{
+        decode_revert_reason,
+        emulate::{emulate_transaction, emulate_trx, setup_syscall_stubs, EmulationLimits},
+    },
     errors::NeonError,
     rpc::Rpc,
     types::TxParams,
@@ -29,8 +34,11 @@ pub async fn trace_transaction(
     accounts: &[Address],
     solana_accounts: &[Pubkey],
     trace_call_config: TraceCallConfig,
+    limits: EmulationLimits<'_>,
 ) -> Result<Value, NeonError> {
     let tracer = new_tracer(&trace_call_config.trace_config)?;
+    let timeout = parse_timeout(&trace_call_config.trace_config)?;
+    let state_diff = needs_state_diff(&trace_call_config.trace_config);
 
     let (emulation_result, _storage) = emulate_transaction(
         rpc_client,
@@ -45,13 +53,13 @@ pub async fn trace_transaction(
         &trace_call_config.block_overrides,
         trace_call_config.state_overrides,
         Some(Rc::clone(&tracer)),
+        timeout,
+        state_diff,
+        limits,
     )
     .await?;
 
-    Ok(Rc::try_unwrap(tracer)
-        .expect("There is must be only one reference")
-        .into_inner()
-        .into_traces(emulation_result))
+    Ok(into_traces(tracer, emulation_result))
 }
 
 #[derive(Serialize, Deserialize)]
@@ -75,10 +83,11 @@ pub async fn trace_block(
     accounts: &[Address],
     solana_accounts: &[Pubkey],
     trace_config: &TraceConfig,
+    limits: EmulationLimits<'_>,
 ) -> Result<TraceBlockReturn, NeonError> {
     setup_syscall_stubs(rpc_client).await?;
 
-    let storage = EmulatorAccountStorage::with_accounts(
+    let mut storage = EmulatorAccountStorage::with_accounts(
         rpc_client,
         evm_loader,
         token,
@@ -90,10 +99,12 @@ pub async fn trace_block(
         None,
     )
     .await?;
+    storage.set_max_accounts_touched(limits.max_accounts_touched);
 
     let mut results = vec![];
     for tx_params in transactions {
-        let result = trace_trx(tx_params, &storage, chain_id, steps, trace_config).await?;
+        let result =
+            trace_trx(tx_params, &storage, chain_id, steps, trace_config, limits).await?;
         results.push(result);
     }
 
@@ -106,8 +117,10 @@ async fn trace_trx<'a>(
     chain_id: u64,
     steps: u64,
     trace_config: &TraceConfig,
+    limits: EmulationLimits<'_>,
 ) -> Result<Value, NeonError> {
     let tracer = new_tracer(trace_config)?;
+    let timeout = parse_timeout(trace_config)?;
 
     let emulation_result = emulate_trx(
         tx_params,
@@ -115,11 +128,120 @@ async fn trace_trx<'a>(
         chain_id,
         steps,
         Some(Rc::clone(&tracer)),
+        timeout,
+        needs_state_diff(trace_config),
+        limits,
     )
     .await?;
 
-    Ok(Rc::try_unwrap(tracer)
+    Ok(into_traces(tracer, emulation_result))
+}
+
+/// Emulation stops on the step limit only when the timeout is exceeded,
+/// in this case the partial trace is returned with an error
+fn into_traces(tracer: TracerType, emulation_result: EmulationResult) -> Value {
+    let timed_out = emulation_result.exit_status == ExitStatus::StepLimit;
+    let revert_reason = match &emulation_result.exit_status {
+        ExitStatus::Revert(data) => decode_revert_reason(data),
+        _ => None,
+    };
+
+    let mut traces = Rc::try_unwrap(tracer)
         .expect("There is must be only one reference")
         .into_inner()
-        .into_traces(emulation_result))
+        .into_traces(emulation_result);
+
+    if timed_out {
+        if let Value::Object(traces) = &mut traces {
+            traces.insert("error".to_string(), "execution timeout".into());
+        }
+    }
+
+    if let (Some(revert_reason), Value::Object(traces)) = (revert_reason, &mut traces) {
+        traces
+            .entry("revertReason")
+            .or_insert_with(|| revert_reason.into());
+    }
+
+    traces
+}
+
+/// Tracing is not limited in time by default
+fn parse_timeout(trace_config: &TraceConfig) -> Result<Option<Duration>, NeonError> {
+    let Some(timeout) = &trace_config.timeout else {
+        return Ok(None);
+    };
+
+    parse_duration(timeout)
+        .map(Some)
+        .ok_or_else(|| NeonError::InvalidTraceTimeout(timeout.clone()))
+}
+
+/// Parses a Go style duration, e.g. "300ms", "5s" or "1m30s"
+/// see <https://pkg.go.dev/time#ParseDuration>
+fn parse_duration(value: &str) -> Option<Duration> {
+    let mut rest = value.trim();
+    if rest.is_empty() {
+        return None;
+    }
+
+    let mut seconds = 0_f64;
+    while !rest.is_empty() {
+        let number_len = rest.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+        let (number, tail) = rest.split_at(number_len);
+        let number: f64 = number.parse().ok()?;
+
+        let unit_len = tail
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_len);
+        let unit_seconds = match unit {
+            "ns" => 1e-9,
+            "us" | "\u{b5}s" => 1e-6,
+            "ms" => 1e-3,
+            "s" => 1.0,
+            "m" => 60.0,
+            "h" => 3600.0,
+            _ => return None,
+        };
+
+        seconds += number * unit_seconds;
+        rest = tail;
+    }
+
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_duration_units() {
+        assert_eq!(parse_duration("5s"), Some(Duration::from_secs(5)));
+        assert_eq!(parse_duration("1.5s"), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("1m30s"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration("2h"), Some(Duration::from_secs(7200)));
+
+        for (value, seconds) in [
+            ("300ms", 0.3),
+            ("20us", 2e-5),
+            ("20\u{b5}s", 2e-5),
+            ("7ns", 7e-9),
+        ] {
+            let parsed = parse_duration(value).unwrap().as_secs_f64();
+            assert!((parsed - seconds).abs() < 1e-9, "{value}");
+        }
+    }
+
+    #[test]
+    fn parse_duration_invalid() {
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("  "), None);
+        assert_eq!(parse_duration("5"), None);
+        assert_eq!(parse_duration("1m30"), None);
+        assert_eq!(parse_duration("5d"), None);
+        assert_eq!(parse_duration("s"), None);
+        assert_eq!(parse_duration(&format!("{}h", "9".repeat(30))), None);
+    }
 }
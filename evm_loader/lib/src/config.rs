@@ -1,6 +1,7 @@
 use std::{env, str::FromStr};
 
 use crate::{types::ChDbConfig, NeonError};
+use evm_loader::types::Address;
 use serde::{Deserialize, Serialize};
 use solana_clap_utils::{
     input_validators::normalize_to_url_if_moniker, keypair::keypair_from_path,
@@ -17,6 +18,13 @@ pub struct Config {
     pub db_config: Option<ChDbConfig>,
     pub json_rpc_url: String,
     pub keypair_path: String,
+    pub emulation_denylist: Vec<Address>,
+    /// Chain ids served by the API, all chain ids are served if empty
+    pub served_chain_ids: Vec<u64>,
+    /// Maximum number of distinct Solana accounts an emulation may touch, unlimited if not set
+    pub max_accounts_touched: Option<usize>,
+    /// Maximum number of events a transaction may emit during emulation, unlimited if not set
+    pub max_logs: Option<usize>,
 }
 
 // impl Debug for Config {
@@ -65,6 +73,10 @@ pub fn create_from_api_config(api_config: &APIOptions) -> Result<Config, NeonErr
         db_config,
         json_rpc_url,
         keypair_path,
+        emulation_denylist: api_config.emulation_denylist.clone(),
+        served_chain_ids: api_config.served_chain_ids.clone(),
+        max_accounts_touched: api_config.max_accounts_touched,
+        max_logs: api_config.max_logs,
     })
 }
 
@@ -77,11 +89,35 @@ pub struct APIOptions {
     pub keypair: String,
     pub fee_payer: String,
     pub db_config: ChDbConfig,
+    #[serde(default)]
+    pub emulation_denylist: Vec<Address>,
+    #[serde(default)]
+    pub served_chain_ids: Vec<u64>,
+    #[serde(default)]
+    pub max_accounts_touched: Option<usize>,
+    #[serde(default)]
+    pub max_logs: Option<usize>,
+    /// Origins allowed to make cross-origin requests, none if empty
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+    /// Methods allowed in cross-origin requests, `GET` and `POST` if empty
+    #[serde(default)]
+    pub allowed_methods: Vec<String>,
+    /// Maximum size of a JSON request body in bytes
+    #[serde(default = "default_max_request_size")]
+    pub max_request_size: usize,
+}
+
+/// Matches the default JSON body limit of actix-web
+pub const DEFAULT_MAX_REQUEST_SIZE: usize = 2 * 1024 * 1024;
+
+const fn default_max_request_size() -> usize {
+    DEFAULT_MAX_REQUEST_SIZE
 }
 
 /// # Errors
-#[must_use]
-pub fn load_api_config_from_enviroment() -> APIOptions {
+/// `InvalidEnvironmentValue` - if a list or a limit cannot be parsed
+pub fn load_api_config_from_enviroment() -> Result<APIOptions, NeonError> {
     let solana_cli_config_path: Option<String> =
         env::var("SOLANA_CLI_CONFIG_PATH").map(Some).unwrap_or(None);
 
@@ -99,7 +135,23 @@ pub fn load_api_config_from_enviroment() -> APIOptions {
 
     let db_config = load_db_config_from_enviroment();
 
-    APIOptions {
+    let emulation_denylist = load_emulation_denylist_from_enviroment()?;
+
+    let served_chain_ids = load_served_chain_ids_from_enviroment()?;
+
+    let max_accounts_touched = load_max_accounts_touched_from_enviroment()?;
+
+    let max_logs = load_max_logs_from_enviroment()?;
+
+    let allowed_origins = load_list_from_enviroment("NEON_API_ALLOWED_ORIGINS");
+
+    let allowed_methods = load_list_from_enviroment("NEON_API_ALLOWED_METHODS");
+
+    let max_request_size = env::var("NEON_API_MAX_REQUEST_SIZE")
+        .map(|size| usize::from_str(size.trim()).expect("max request size parse error"))
+        .unwrap_or(DEFAULT_MAX_REQUEST_SIZE);
+
+    Ok(APIOptions {
         solana_cli_config_path,
         commitment,
         json_rpc_url,
@@ -107,7 +159,77 @@ pub fn load_api_config_from_enviroment() -> APIOptions {
         keypair,
         fee_payer,
         db_config,
-    }
+        emulation_denylist,
+        served_chain_ids,
+        max_accounts_touched,
+        max_logs,
+        allowed_origins,
+        allowed_methods,
+        max_request_size,
+    })
+}
+
+/// Comma separated list of values
+fn load_list_from_enviroment(name: &str) -> Vec<String> {
+    env::var(name)
+        .map(|values| {
+            values
+                .split(',')
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_owned)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Comma separated list of values parsed as `T`
+fn load_parsed_list_from_enviroment<T: FromStr>(name: &str) -> Result<Vec<T>, NeonError> {
+    load_list_from_enviroment(name)
+        .iter()
+        .map(|value| parse_enviroment_value(name, value))
+        .collect()
+}
+
+/// Optional value parsed as `T`
+fn load_parsed_from_enviroment<T: FromStr>(name: &str) -> Result<Option<T>, NeonError> {
+    env::var(name)
+        .ok()
+        .map(|value| parse_enviroment_value(name, value.trim()))
+        .transpose()
+}
+
+fn parse_enviroment_value<T: FromStr>(name: &str, value: &str) -> Result<T, NeonError> {
+    T::from_str(value)
+        .map_err(|_| NeonError::InvalidEnvironmentValue(name.to_owned(), value.to_owned()))
+}
+
+/// Comma separated list of contract addresses which must not be emulated
+/// # Errors
+/// `InvalidEnvironmentValue` - if an address cannot be parsed
+pub fn load_emulation_denylist_from_enviroment() -> Result<Vec<Address>, NeonError> {
+    load_parsed_list_from_enviroment("NEON_EMULATION_DENYLIST")
+}
+
+/// Comma separated list of chain ids accepted by the API
+/// # Errors
+/// `InvalidEnvironmentValue` - if a chain id cannot be parsed
+pub fn load_served_chain_ids_from_enviroment() -> Result<Vec<u64>, NeonError> {
+    load_parsed_list_from_enviroment("NEON_SERVED_CHAIN_IDS")
+}
+
+/// Maximum number of distinct Solana accounts an emulation may touch
+/// # Errors
+/// `InvalidEnvironmentValue` - if the limit cannot be parsed
+pub fn load_max_accounts_touched_from_enviroment() -> Result<Option<usize>, NeonError> {
+    load_parsed_from_enviroment("NEON_MAX_ACCOUNTS_TOUCHED")
+}
+
+/// Maximum number of events a transaction may emit during emulation
+/// # Errors
+/// `InvalidEnvironmentValue` - if the limit cannot be parsed
+pub fn load_max_logs_from_enviroment() -> Result<Option<usize>, NeonError> {
+    load_parsed_from_enviroment("NEON_MAX_LOGS")
 }
 
 /// # Errors
@@ -134,3 +256,38 @@ fn load_db_config_from_enviroment() -> ChDbConfig {
         clickhouse_password,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsed_list_skips_empty_values() {
+        env::set_var("NEON_TEST_PARSED_LIST", " 111, ,112,");
+        let values: Vec<u64> = load_parsed_list_from_enviroment("NEON_TEST_PARSED_LIST").unwrap();
+        assert_eq!(values, vec![111, 112]);
+
+        let values: Vec<u64> = load_parsed_list_from_enviroment("NEON_TEST_UNSET_LIST").unwrap();
+        assert!(values.is_empty());
+    }
+
+    #[test]
+    fn invalid_values_are_errors() {
+        env::set_var("NEON_TEST_INVALID_LIST", "111,0xzz");
+        assert!(matches!(
+            load_parsed_list_from_enviroment::<u64>("NEON_TEST_INVALID_LIST"),
+            Err(NeonError::InvalidEnvironmentValue(name, value))
+                if name == "NEON_TEST_INVALID_LIST" && value == "0xzz"
+        ));
+
+        env::set_var("NEON_TEST_INVALID_LIMIT", "ten");
+        assert!(matches!(
+            load_parsed_from_enviroment::<usize>("NEON_TEST_INVALID_LIMIT"),
+            Err(NeonError::InvalidEnvironmentValue(_, value)) if value == "ten"
+        ));
+        assert!(matches!(
+            load_parsed_from_enviroment::<usize>("NEON_TEST_UNSET_LIMIT"),
+            Ok(None)
+        ));
+    }
+}
@@ -10,6 +10,7 @@ use solana_client::tpu_client::TpuSenderError as SolanaTpuSenderError;
 use solana_sdk::program_error::ProgramError as SolanaProgramError;
 use solana_sdk::pubkey::{Pubkey, PubkeyError as SolanaPubkeyError};
 use solana_sdk::signer::SignerError as SolanaSignerError;
+use evm_loader::types::Address;
 use thiserror::Error;
 
 use crate::commands::init_environment::EnvironmentError;
@@ -97,6 +98,26 @@ pub enum NeonError {
     ClickHouse(ChError),
     #[error("Slot {0} is less than earliest_rooted_slot={1}")]
     EarlySlot(u64, u64),
+    /// too many logs
+    #[error("Too many logs emitted by {0}")]
+    TooManyLogs(Address),
+    /// address is on the emulation denylist
+    #[error("Emulation of {0} is denied")]
+    AddressDenied(Address),
+    /// chain id is not in the served chain ids list
+    #[error("Chain id {0} is not served")]
+    ChainIdNotServed(u64),
+    #[error("Invalid trace timeout {0:?}.")]
+    InvalidTraceTimeout(String),
+    /// emulation touched more accounts than allowed
+    #[error("Too many accounts touched, the limit is {0}")]
+    TooManyAccountsTouched(usize),
+    /// storage value has non-zero bytes above the low 20 bytes of an address
+    #[error("Storage value {0} is not an address")]
+    StorageValueNotAddress(String),
+    /// environment variable has a value which cannot be parsed
+    #[error("Invalid value {1:?} of the environment variable {0}")]
+    InvalidEnvironmentValue(String, String),
 }
 
 impl NeonError {
@@ -132,6 +153,13 @@ impl NeonError {
             NeonError::TxParametersParsingError(_) => 250,
             NeonError::ClickHouse(_) => 252,
             NeonError::EarlySlot(_, _) => 253,
+            NeonError::TooManyLogs(_) => 254,
+            NeonError::AddressDenied(_) => 255,
+            NeonError::ChainIdNotServed(_) => 243,
+            NeonError::InvalidTraceTimeout(_) => 244,
+            NeonError::TooManyAccountsTouched(_) => 251,
+            NeonError::StorageValueNotAddress(_) => 240,
+            NeonError::InvalidEnvironmentValue(_, _) => 204,
         }
     }
 }
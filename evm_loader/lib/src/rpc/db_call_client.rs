@@ -91,16 +91,10 @@ impl Rpc for CallDbClient {
         &self,
         pubkeys: &[Pubkey],
     ) -> ClientResult<Vec<Option<Account>>> {
-        let mut result = Vec::new();
-        for key in pubkeys {
-            let account = self
-                .tracer_db
-                .get_account_at(key, self.slot)
-                .await
-                .map_err(|e| e!("load account error", key, e))?;
-            result.push(account);
-        }
-        Ok(result)
+        self.tracer_db
+            .get_accounts_at(pubkeys, self.slot)
+            .await
+            .map_err(|e| e!("load accounts error", pubkeys, e))
     }
 
     async fn get_account_data(&self, key: &Pubkey) -> ClientResult<Vec<u8>> {
@@ -1,3 +1,4 @@
+use crate::commands::get_storage_at::StorageValueEncoding;
 use crate::types::{PubkeyBase58, TxParams};
 use ethnum::U256;
 use evm_loader::evm::tracing::TraceCallConfig;
@@ -20,6 +21,16 @@ pub struct GetStorageAtRequest {
     pub contract_id: Address,
     pub index: U256,
     pub slot: Option<u64>,
+    #[serde(default)]
+    pub encoding: StorageValueEncoding,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct PrepareDeployRequest {
+    pub sender: Address,
+    pub data: Option<Vec<u8>>,
+    pub salt: Option<U256>,
+    pub slot: Option<u64>,
 }
 
 #[derive(Deserialize, Serialize, Default)]
@@ -107,6 +107,37 @@ impl TryInto<Account> for AccountRow {
     }
 }
 
+/// `AccountRow` together with the account key, returned by batched queries
+#[derive(Row, serde::Deserialize, Clone)]
+pub struct PubkeyAccountRow {
+    pub pubkey: Vec<u8>,
+    pub owner: Vec<u8>,
+    pub lamports: u64,
+    pub executable: bool,
+    pub rent_epoch: u64,
+    pub data: Vec<u8>,
+    pub txn_signature: Vec<Option<u8>>,
+}
+
+impl From<PubkeyAccountRow> for AccountRow {
+    fn from(row: PubkeyAccountRow) -> Self {
+        AccountRow {
+            owner: row.owner,
+            lamports: row.lamports,
+            executable: row.executable,
+            rent_epoch: row.rent_epoch,
+            data: row.data,
+            txn_signature: row.txn_signature,
+        }
+    }
+}
+
+#[derive(Debug, Row, serde::Deserialize, Clone)]
+pub struct PubkeySlot {
+    pub pubkey: Vec<u8>,
+    pub slot: u64,
+}
+
 pub enum EthSyncStatus {
     Syncing(EthSyncing),
     Synced,
@@ -1,6 +1,8 @@
 use crate::{
     commands::get_neon_elf::get_elf_parameter,
-    types::tracer_ch_common::{AccountRow, ChError, SlotParent, ROOT_BLOCK_DELAY},
+    types::tracer_ch_common::{
+        AccountRow, ChError, PubkeyAccountRow, PubkeySlot, SlotParent, ROOT_BLOCK_DELAY,
+    },
 };
 
 use super::{
@@ -21,12 +23,20 @@ use std::{
         Ord,
         Ordering::{Equal, Greater, Less},
     },
-    time::Instant,
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
 
+/// How long a resolved branch may be reused for the same requested slot
+const BRANCH_CACHE_TTL: Duration = Duration::from_secs(10);
+
+type BranchCache = HashMap<u64, (Instant, (u64, Vec<u64>))>;
+
 #[derive(Clone)]
 pub struct ClickHouseDb {
     pub client: Client,
+    branch_cache: Arc<Mutex<BranchCache>>,
 }
 
 impl ClickHouseDb {
@@ -43,7 +53,10 @@ impl ClickHouseDb {
                 .with_password(password),
         };
 
-        ClickHouseDb { client }
+        ClickHouseDb {
+            client,
+            branch_cache: Arc::new(Mutex::new(HashMap::new())),
+        }
     }
 
     // return value is not used for tracer methods
@@ -83,6 +96,42 @@ impl ClickHouseDb {
         result
     }
 
+    pub async fn is_slot_rooted(&self, slot: Slot) -> ChResult<bool> {
+        let time_start = Instant::now();
+        let query = "SELECT count() FROM events.rooted_slots WHERE slot = ?";
+        let result = self
+            .client
+            .query(query)
+            .bind(slot)
+            .fetch_one::<u64>()
+            .await
+            .map(|count| count > 0)
+            .map_err(std::convert::Into::into);
+        let execution_time = Instant::now().duration_since(time_start);
+        info!(
+            "is_slot_rooted sql returned {result:?}, time: {} sec",
+            execution_time.as_secs_f64()
+        );
+        result
+    }
+
+    pub async fn ping(&self) -> ChResult<()> {
+        let time_start = Instant::now();
+        let result = self
+            .client
+            .query("SELECT 1")
+            .fetch_one::<u8>()
+            .await
+            .map(|_| ())
+            .map_err(std::convert::Into::into);
+        let execution_time = Instant::now().duration_since(time_start);
+        info!(
+            "ping sql returned {result:?}, time: {} sec",
+            execution_time.as_secs_f64()
+        );
+        result
+    }
+
     pub async fn get_latest_block(&self) -> ChResult<u64> {
         let time_start = Instant::now();
         let query = "SELECT max(slot) FROM events.update_slot";
@@ -100,7 +149,30 @@ impl ClickHouseDb {
         result
     }
 
-    async fn get_branch_slots(&self, slot: Option<u64>) -> ChResult<(u64, Vec<u64>)> {
+    /// `get_branch_slots` for a requested slot, reusing branches resolved within `BRANCH_CACHE_TTL`
+    /// Only branches of rooted slots are cached, a branch of a non-rooted slot may still change
+    async fn get_cached_branch_slots(&self, slot: u64) -> ChResult<(u64, Vec<u64>)> {
+        if let Some((resolved_at, branch)) = self.branch_cache.lock().unwrap().get(&slot) {
+            if resolved_at.elapsed() < BRANCH_CACHE_TTL {
+                return Ok(branch.clone());
+            }
+        }
+
+        let (first, branch, is_rooted) = self.get_branch_slots(Some(slot)).await?;
+        let branch = (first, branch);
+        if !is_rooted {
+            return Ok(branch);
+        }
+
+        let mut cache = self.branch_cache.lock().unwrap();
+        cache.retain(|_, (resolved_at, _)| resolved_at.elapsed() < BRANCH_CACHE_TTL);
+        cache.insert(slot, (Instant::now(), branch.clone()));
+
+        Ok(branch)
+    }
+
+    /// Returns the first slot, the branch slots and whether the branch starts at a rooted slot
+    async fn get_branch_slots(&self, slot: Option<u64>) -> ChResult<(u64, Vec<u64>, bool)> {
         fn branch_from(
             rows: Vec<SlotParent>,
             test_start: &dyn Fn(&SlotParent) -> bool,
@@ -161,8 +233,9 @@ impl ClickHouseDb {
 
         let result = if let Some(slot) = slot {
             match slot.cmp(&first.slot) {
-                Less | Equal => Ok((slot, vec![])),
+                Less | Equal => Ok((slot, vec![], true)),
                 Greater => {
+                    let is_rooted = rows.iter().any(|row| row.slot == slot && row.is_rooted());
                     let branch = branch_from(rows, &|row| row.slot == slot);
                     if branch.is_empty() {
                         let err = clickhouse::error::Error::Custom(format!(
@@ -170,12 +243,12 @@ impl ClickHouseDb {
                         ));
                         return Err(ChError::Db(err));
                     }
-                    Ok((first.slot, branch))
+                    Ok((first.slot, branch, is_rooted))
                 }
             }
         } else {
             let branch = branch_from(rows, &SlotParent::is_rooted);
-            Ok((first.slot, branch))
+            Ok((first.slot, branch, true))
         };
 
         debug!("get_branch_slots {{ slot: {slot:?} }} -> {result:?}");
@@ -223,7 +296,7 @@ impl ClickHouseDb {
     #[allow(clippy::too_many_lines)]
     pub async fn get_account_at(&self, pubkey: &Pubkey, slot: u64) -> ChResult<Option<Account>> {
         info!("get_account_at {{ pubkey: {pubkey}, slot: {slot} }}");
-        let (first, mut branch) = self.get_branch_slots(Some(slot)).await.map_err(|e| {
+        let (first, mut branch) = self.get_cached_branch_slots(slot).await.map_err(|e| {
             println!("get_branch_slots error: {:?}", e);
             e
         })?;
@@ -298,6 +371,143 @@ impl ClickHouseDb {
         result
     }
 
+    /// Batched version of `get_account_at`, the branch is resolved once
+    /// and every tier is queried for all remaining pubkeys at once
+    pub async fn get_accounts_at(
+        &self,
+        pubkeys: &[Pubkey],
+        slot: u64,
+    ) -> ChResult<Vec<Option<Account>>> {
+        info!("get_accounts_at {{ pubkeys: {}, slot: {slot} }}", pubkeys.len());
+        if pubkeys.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let (first, mut slots) = self.get_cached_branch_slots(slot).await?;
+
+        let pubkey_strs: Vec<String> = pubkeys
+            .iter()
+            .map(|pubkey| format!("{:?}", pubkey.to_bytes()))
+            .collect();
+
+        // Rows older than the branch are at most at the latest rooted slot of each account,
+        // so a single slot set serves all of the pubkeys
+        slots.extend(self.get_accounts_rooted_slots(&pubkey_strs, first).await?);
+        slots.sort_unstable();
+        slots.dedup();
+
+        let mut rows = HashMap::new();
+        if !slots.is_empty() {
+            let query = r#"
+                SELECT pubkey, owner, lamports, executable, rent_epoch, data, txn_signature
+                FROM events.update_account_distributed
+                WHERE pubkey IN ?
+                  AND slot IN ?
+                ORDER BY pubkey, slot DESC, write_version DESC
+                LIMIT 1 BY pubkey
+            "#;
+
+            let time_start = Instant::now();
+            let found = self
+                .client
+                .query(query)
+                .bind(pubkey_strs.as_slice())
+                .bind(slots.as_slice())
+                .fetch_all::<PubkeyAccountRow>()
+                .await?;
+            let execution_time = Instant::now().duration_since(time_start);
+            info!(
+                "get_accounts_at {{ slot: {slot} }} sql(1) returned {} row(s), time: {} sec",
+                found.len(),
+                execution_time.as_secs_f64()
+            );
+
+            rows.extend(found.into_iter().map(|row| (row.pubkey.clone(), row)));
+        }
+
+        let missing: Vec<String> = pubkeys
+            .iter()
+            .zip(&pubkey_strs)
+            .filter(|(pubkey, _)| !rows.contains_key(&pubkey.to_bytes()[..]))
+            .map(|(_, pubkey_str)| pubkey_str.clone())
+            .collect();
+
+        if !missing.is_empty() {
+            let query = r#"
+                SELECT pubkey, owner, lamports, executable, rent_epoch, data, txn_signature
+                FROM events.older_account_distributed FINAL
+                WHERE pubkey IN ? AND slot <= ?
+                ORDER BY pubkey, slot DESC
+                LIMIT 1 BY pubkey
+            "#;
+
+            let time_start = Instant::now();
+            let found = self
+                .client
+                .query(query)
+                .bind(missing.as_slice())
+                .bind(slot)
+                .fetch_all::<PubkeyAccountRow>()
+                .await?;
+            let execution_time = Instant::now().duration_since(time_start);
+            info!(
+                "get_accounts_at {{ slot: {slot} }} sql(2) returned {} row(s), time: {} sec",
+                found.len(),
+                execution_time.as_secs_f64()
+            );
+
+            rows.extend(found.into_iter().map(|row| (row.pubkey.clone(), row)));
+        }
+
+        pubkeys
+            .iter()
+            .map(|pubkey| {
+                rows.remove(&pubkey.to_bytes()[..])
+                    .map(|row| TryInto::<Account>::try_into(AccountRow::from(row)))
+                    .transpose()
+                    .map_err(|err| ChError::Db(clickhouse::error::Error::Custom(err)))
+            })
+            .collect()
+    }
+
+    async fn get_accounts_rooted_slots(
+        &self,
+        pubkeys: &[String],
+        slot: u64,
+    ) -> ChResult<Vec<u64>> {
+        let query = r#"
+        SELECT uad.pubkey AS pubkey, max(uad.slot) AS slot
+        FROM events.update_account_distributed AS uad
+        WHERE uad.pubkey IN ?
+          AND uad.slot <= ?
+          AND (
+            SELECT COUNT(slot)
+            FROM events.rooted_slots
+            WHERE slot = ?
+          ) >= 1
+        GROUP BY uad.pubkey
+        "#;
+
+        let time_start = Instant::now();
+        let rows = self
+            .client
+            .query(query)
+            .bind(pubkeys)
+            .bind(slot)
+            .bind(slot)
+            .fetch_all::<PubkeySlot>()
+            .await?;
+
+        let execution_time = Instant::now().duration_since(time_start);
+        info!(
+            "get_accounts_rooted_slots {{ slot: {slot} }} sql(1) returned {} row(s), time: {} sec",
+            rows.len(),
+            execution_time.as_secs_f64(),
+        );
+
+        Ok(rows.into_iter().map(|row| row.slot).collect())
+    }
+
     async fn get_older_account_row_at(
         &self,
         pubkey: &str,
@@ -354,7 +564,7 @@ impl ClickHouseDb {
     }
 
     async fn get_sol_sig_confirmed_slot(&self, sol_sig: &[u8; 64]) -> ChResult<Option<SlotParent>> {
-        let (_, slot_vec) = self.get_branch_slots(None).await?;
+        let (_, slot_vec, _) = self.get_branch_slots(None).await?;
         let query = r#"
             SELECT slot, parent, status
             FROM events.update_slot
@@ -21,6 +21,8 @@ mod apply;
 mod backend;
 #[cfg(target_os = "solana")]
 mod base;
+#[cfg(test)]
+pub mod test_storage;
 
 #[derive(Debug)]
 pub enum AccountOperation {
@@ -109,6 +111,12 @@ pub trait AccountStorage {
     /// Solana account data len
     async fn solana_account_space(&self, address: &Address) -> Option<usize>;
 
+    /// The limit if the emulation tried to touch more accounts than allowed
+    #[cfg(not(target_os = "solana"))]
+    fn exceeded_accounts_limit(&self) -> Option<usize> {
+        None
+    }
+
     async fn calc_accounts_operations(&self, actions: &[Action]) -> AccountsOperations {
         let mut accounts = HashMap::new();
         for action in actions {
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use ethnum::U256;
+use maybe_async::maybe_async;
+use solana_program::{
+    account_info::{AccountInfo, IntoAccountInfo},
+    pubkey::Pubkey,
+};
+
+use super::AccountStorage;
+use crate::{evm::Buffer, executor::OwnedAccountInfo, types::Address};
+
+#[derive(Default)]
+struct Account {
+    nonce: u64,
+    balance: U256,
+    code: Vec<u8>,
+}
+
+/// In-memory `AccountStorage` for running the executor in tests
+#[derive(Default)]
+pub struct TestAccountStorage {
+    program_id: Pubkey,
+    accounts: HashMap<Address, Account>,
+    solana_accounts: HashMap<Pubkey, OwnedAccountInfo>,
+}
+
+impl TestAccountStorage {
+    /// Existing account with `code`, an empty `code` makes it an externally owned account
+    pub fn add_account(&mut self, address: Address, code: &[u8]) {
+        let account = Account {
+            nonce: 1,
+            balance: U256::ZERO,
+            code: code.to_vec(),
+        };
+        self.accounts.insert(address, account);
+    }
+
+    /// Solana account owned by an external program
+    pub fn add_solana_account(&mut self, key: Pubkey, data: Vec<u8>) {
+        let account = OwnedAccountInfo {
+            key,
+            is_signer: false,
+            is_writable: false,
+            lamports: 1_000_000_000,
+            data,
+            owner: Pubkey::new_unique(),
+            executable: false,
+            rent_epoch: 0,
+        };
+        self.solana_accounts.insert(key, account);
+    }
+}
+
+#[maybe_async(?Send)]
+impl AccountStorage for TestAccountStorage {
+    fn neon_token_mint(&self) -> &Pubkey {
+        &crate::config::token_mint::ID
+    }
+
+    fn program_id(&self) -> &Pubkey {
+        &self.program_id
+    }
+
+    fn operator(&self) -> &Pubkey {
+        &self.program_id
+    }
+
+    fn block_number(&self) -> U256 {
+        U256::ZERO
+    }
+
+    fn block_timestamp(&self) -> U256 {
+        U256::ZERO
+    }
+
+    async fn block_hash(&self, _number: u64) -> [u8; 32] {
+        [0; 32]
+    }
+
+    fn chain_id(&self) -> u64 {
+        111
+    }
+
+    async fn exists(&self, address: &Address) -> bool {
+        self.accounts.contains_key(address)
+    }
+
+    async fn nonce(&self, address: &Address) -> u64 {
+        self.accounts.get(address).map_or(0, |a| a.nonce)
+    }
+
+    async fn balance(&self, address: &Address) -> U256 {
+        self.accounts.get(address).map_or(U256::ZERO, |a| a.balance)
+    }
+
+    async fn code_size(&self, address: &Address) -> usize {
+        self.accounts.get(address).map_or(0, |a| a.code.len())
+    }
+
+    async fn code_hash(&self, address: &Address) -> [u8; 32] {
+        let code = self.accounts.get(address).map_or(&[][..], |a| a.code.as_slice());
+        solana_program::keccak::hash(code).to_bytes()
+    }
+
+    async fn code(&self, address: &Address) -> Buffer {
+        self.accounts
+            .get(address)
+            .map_or_else(Buffer::empty, |a| Buffer::from_slice(&a.code))
+    }
+
+    async fn generation(&self, _address: &Address) -> u32 {
+        0
+    }
+
+    async fn storage(&self, _address: &Address, _index: &U256) -> [u8; 32] {
+        [0; 32]
+    }
+
+    async fn clone_solana_account(&self, address: &Pubkey) -> OwnedAccountInfo {
+        self.solana_accounts
+            .get(address)
+            .cloned()
+            .unwrap_or_else(|| OwnedAccountInfo {
+                key: *address,
+                is_signer: false,
+                is_writable: false,
+                lamports: 0,
+                data: vec![],
+                owner: solana_program::system_program::ID,
+                executable: false,
+                rent_epoch: 0,
+            })
+    }
+
+    async fn map_solana_account<F, R>(&self, address: &Pubkey, action: F) -> R
+    where
+        F: FnOnce(&AccountInfo) -> R,
+    {
+        let mut account = self.clone_solana_account(address).await;
+        let info = (&mut account).into_account_info();
+
+        action(&info)
+    }
+
+    async fn solana_account_space(&self, _address: &Address) -> Option<usize> {
+        None
+    }
+}
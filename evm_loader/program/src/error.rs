@@ -83,12 +83,18 @@ pub enum Error {
     #[error("Insufficient balance for transfer, account = {0}, required = {1}")]
     InsufficientBalance(Address, U256),
 
+    #[error("Insufficient balance for gas fee, account = {0}, required = {1}")]
+    InsufficientBalanceForFee(Address, U256),
+
     #[error("Out of Gas, limit = {0}, required = {1}")]
     OutOfGas(U256, U256),
 
     #[error("EVM Stack Overflow")]
     StackOverflow,
 
+    #[error("EVM call depth exceeds limit {0}")]
+    CallTooDeep(usize),
+
     #[error("EVM Stack Underflow")]
     StackUnderflow,
 
@@ -140,6 +146,21 @@ pub enum Error {
     #[error("Index out of bounds")]
     OutOfBounds,
 
+    #[error("Too many logs emitted, contract = {0}")]
+    TooManyLogs(Address),
+
+    #[error("Call to a denied contract, contract = {0}")]
+    AddressDenied(Address),
+
+    #[error("Too many accounts touched, the limit is {0}")]
+    TooManyAccountsTouched(usize),
+
+    #[error("Transaction size {0} exceeds limit {1}")]
+    TransactionTooLarge(usize, usize),
+
+    #[error("External account {0} data size {1} exceeds limit {2}")]
+    ExternalAccountTooLarge(Pubkey, usize, usize),
+
     #[error("Holder Account - invalid owner {0}, expected = {1}")]
     HolderInvalidOwner(Pubkey, Pubkey),
 
@@ -212,7 +233,7 @@ macro_rules! E {
 }
 
 #[must_use]
-fn format_revert_error(msg: &[u8]) -> Option<&str> {
+pub fn format_revert_error(msg: &[u8]) -> Option<&str> {
     if msg.starts_with(&[0x08, 0xc3, 0x79, 0xa0]) {
         // Error(string) function selector
         let msg = &msg[4..];
@@ -239,7 +260,7 @@ fn format_revert_error(msg: &[u8]) -> Option<&str> {
 }
 
 #[must_use]
-fn format_revert_panic(msg: &[u8]) -> Option<U256> {
+pub fn format_revert_panic(msg: &[u8]) -> Option<U256> {
     if msg.starts_with(&[0x4e, 0x48, 0x7b, 0x71]) {
         // Panic(uint256) function selector
         let msg = &msg[4..];
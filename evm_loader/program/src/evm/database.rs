@@ -42,4 +42,10 @@ pub trait Database {
         data: &[u8],
         is_static: bool,
     ) -> Option<Result<Vec<u8>>>;
+
+    /// The limit if the emulation tried to touch more accounts than allowed
+    #[cfg(not(target_os = "solana"))]
+    fn exceeded_accounts_limit(&self) -> Option<usize> {
+        None
+    }
 }
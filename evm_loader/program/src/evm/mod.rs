@@ -11,9 +11,12 @@ use solana_program::log::sol_log_data;
 
 pub use buffer::Buffer;
 
+#[cfg(not(target_os = "solana"))]
+use crate::config::CREATE_STACK_LIMIT;
 #[cfg(not(target_os = "solana"))]
 use crate::evm::tracing::TracerTypeOpt;
 use crate::{
+    config::CALL_STACK_LIMIT,
     error::{build_revert_message, Error, Result},
     evm::{opcode::Action, precompile::is_precompile_address},
     types::{Address, Transaction},
@@ -28,6 +31,8 @@ mod opcode;
 mod opcode_table;
 mod precompile;
 mod stack;
+#[cfg(test)]
+mod test_database;
 #[cfg(not(target_os = "solana"))]
 pub mod tracing;
 mod utils;
@@ -91,6 +96,29 @@ impl ExitStatus {
         }
     }
 
+    /// Code logged in the `RETURN` event of a finished transaction,
+    /// `None` for the step limit, which never finishes a transaction
+    #[must_use]
+    pub fn code(&self) -> Option<u8> {
+        match self {
+            ExitStatus::Stop => Some(0x11),
+            ExitStatus::Return(_) => Some(0x12),
+            ExitStatus::Suicide => Some(0x13),
+            ExitStatus::Revert(_) => Some(0xd0),
+            ExitStatus::StepLimit => None,
+        }
+    }
+
+    /// `status()` of the exit status logged with `code`
+    #[must_use]
+    pub fn status_by_code(code: u8) -> Option<&'static str> {
+        match code {
+            0x11..=0x13 => Some("succeed"),
+            0xd0 => Some("revert"),
+            _ => None,
+        }
+    }
+
     #[must_use]
     pub fn is_succeed(&self) -> Option<bool> {
         match self {
@@ -156,6 +184,21 @@ pub struct Machine<B: Database> {
     #[cfg(not(target_os = "solana"))]
     #[serde(skip)]
     tracer: TracerTypeOpt,
+
+    /// Number of LOG opcodes allowed before the emulation is aborted
+    #[cfg(not(target_os = "solana"))]
+    #[serde(skip)]
+    logs_left: Option<usize>,
+
+    /// Wall clock deadline of the emulation, reported as `ExitStatus::StepLimit`
+    #[cfg(not(target_os = "solana"))]
+    #[serde(skip)]
+    deadline: Option<std::time::Instant>,
+
+    /// Contracts the emulation is aborted on a call to
+    #[cfg(not(target_os = "solana"))]
+    #[serde(skip)]
+    denylist: Option<std::rc::Rc<[Address]>>,
 }
 
 impl<B: Database> Machine<B> {
@@ -222,9 +265,8 @@ impl<B: Database> Machine<B> {
             }
         }
 
-        if backend.balance(&origin).await? < trx.value() {
-            return Err(Error::InsufficientBalance(origin, trx.value()));
-        }
+        let origin_balance = backend.balance(&origin).await?;
+        trx.validate_sender_balance(origin, origin_balance)?;
 
         if backend.code_size(&origin).await? != 0 {
             return Err(Error::SenderHasDeployedCode(origin));
@@ -293,6 +335,12 @@ impl<B: Database> Machine<B> {
             phantom: PhantomData,
             #[cfg(not(target_os = "solana"))]
             tracer,
+            #[cfg(not(target_os = "solana"))]
+            logs_left: None,
+            #[cfg(not(target_os = "solana"))]
+            deadline: None,
+            #[cfg(not(target_os = "solana"))]
+            denylist: None,
         })
     }
 
@@ -341,9 +389,49 @@ impl<B: Database> Machine<B> {
             phantom: PhantomData,
             #[cfg(not(target_os = "solana"))]
             tracer,
+            #[cfg(not(target_os = "solana"))]
+            logs_left: None,
+            #[cfg(not(target_os = "solana"))]
+            deadline: None,
+            #[cfg(not(target_os = "solana"))]
+            denylist: None,
         })
     }
 
+    /// Abort the emulation with `Error::TooManyLogs` after `limit` LOG opcodes
+    #[cfg(not(target_os = "solana"))]
+    pub fn set_logs_limit(&mut self, limit: usize) {
+        self.logs_left = Some(limit);
+    }
+
+    /// Stop the execution with `ExitStatus::StepLimit` once `deadline` has passed
+    #[cfg(not(target_os = "solana"))]
+    pub fn set_deadline(&mut self, deadline: std::time::Instant) {
+        self.deadline = Some(deadline);
+    }
+
+    /// Abort the emulation with `Error::AddressDenied` on a call to any of `denylist` contracts
+    #[cfg(not(target_os = "solana"))]
+    pub fn set_denylist(&mut self, denylist: &[Address]) {
+        if !denylist.is_empty() {
+            self.denylist = Some(denylist.into());
+        }
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    fn check_denylist(&self, address: &Address) -> Result<()> {
+        match &self.denylist {
+            Some(denylist) if denylist.contains(address) => Err(Error::AddressDenied(*address)),
+            _ => Ok(()),
+        }
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    #[must_use]
+    pub fn is_timed_out(&self) -> bool {
+        self.deadline.map_or(false, |deadline| std::time::Instant::now() >= deadline)
+    }
+
     #[maybe_async]
     pub async fn execute(&mut self, step_limit: u64, backend: &mut B) -> Result<(ExitStatus, u64)> {
         assert!(self.execution_code.is_initialized());
@@ -356,7 +444,8 @@ impl<B: Database> Machine<B> {
             self,
             tracing::Event::BeginVM {
                 context: self.context,
-                code: self.execution_code.to_vec()
+                code: self.execution_code.to_vec(),
+                input: self.call_data.to_vec()
             }
         );
 
@@ -372,6 +461,11 @@ impl<B: Database> Machine<B> {
                     break ExitStatus::StepLimit;
                 }
 
+                #[cfg(not(target_os = "solana"))]
+                if self.is_timed_out() {
+                    break ExitStatus::StepLimit;
+                }
+
                 let opcode = self.execution_code.get_or_default(self.pc);
 
                 tracing_event!(
@@ -384,8 +478,22 @@ impl<B: Database> Machine<B> {
                     }
                 );
 
-                let opcode_result = match self.execute_opcode(backend, opcode).await {
+                let opcode_result = self.execute_opcode(backend, opcode).await;
+
+                // Accounts beyond the limit are not loaded, so the opcode saw incomplete state
+                #[cfg(not(target_os = "solana"))]
+                if let Some(limit) = backend.exceeded_accounts_limit() {
+                    return Err(Error::TooManyAccountsTouched(limit));
+                }
+
+                let opcode_result = match opcode_result {
                     Ok(result) => result,
+                    #[cfg(not(target_os = "solana"))]
+                    Err(Error::TooManyLogs(contract)) => return Err(Error::TooManyLogs(contract)),
+                    #[cfg(not(target_os = "solana"))]
+                    Err(Error::AddressDenied(contract)) => {
+                        return Err(Error::AddressDenied(contract))
+                    }
                     Err(e) => {
                         let message = build_revert_message(&e.to_string());
                         self.opcode_revert_impl(Buffer::from_slice(&message), backend)
@@ -446,12 +554,50 @@ impl<B: Database> Machine<B> {
             phantom: PhantomData,
             #[cfg(not(target_os = "solana"))]
             tracer: self.tracer.clone(),
+            #[cfg(not(target_os = "solana"))]
+            logs_left: self.logs_left,
+            #[cfg(not(target_os = "solana"))]
+            deadline: self.deadline,
+            #[cfg(not(target_os = "solana"))]
+            denylist: self.denylist.clone(),
         };
 
         core::mem::swap(self, &mut other);
         self.parent = Some(Box::new(other));
     }
 
+    /// Number of call frames above this one.
+    /// Walks the parent frames, so the layout of the serialized `Machine` is not changed.
+    fn depth(&self) -> usize {
+        std::iter::successors(self.parent.as_deref(), |machine| machine.parent.as_deref())
+            .count()
+    }
+
+    /// Number of contract creations among this frame and the ones above it,
+    /// the transaction frame is not counted
+    #[cfg(not(target_os = "solana"))]
+    fn create_depth(&self) -> usize {
+        std::iter::successors(Some(self), |machine| machine.parent.as_deref())
+            .filter(|machine| machine.parent.is_some() && machine.reason == Reason::Create)
+            .count()
+    }
+
+    /// Fails the call frame just entered if it is nested deeper than `CALL_STACK_LIMIT`.
+    /// In emulation it also fails if it is nested in more than `CREATE_STACK_LIMIT`
+    /// contract creations, the program does not limit contract creations separately.
+    fn check_call_depth(&self) -> Result<()> {
+        if self.depth() > CALL_STACK_LIMIT {
+            return Err(Error::CallTooDeep(CALL_STACK_LIMIT));
+        }
+
+        #[cfg(not(target_os = "solana"))]
+        if self.create_depth() > CREATE_STACK_LIMIT {
+            return Err(Error::CallTooDeep(CREATE_STACK_LIMIT));
+        }
+
+        Ok(())
+    }
+
     fn join(&mut self) -> Self {
         assert!(self.parent.is_some());
 
@@ -460,4 +606,201 @@ impl<B: Database> Machine<B> {
 
         other
     }
+
+    /// `join` for a frame whose changes are committed:
+    /// LOG opcodes of a reverted frame don't count towards the limit
+    fn join_committed(&mut self) -> Self {
+        let other = self.join();
+
+        #[cfg(not(target_os = "solana"))]
+        {
+            self.logs_left = other.logs_left;
+        }
+
+        other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_database::{transaction, TestDatabase};
+    use super::*;
+
+    #[test]
+    fn exit_status_code_round_trip() {
+        let statuses = [
+            ExitStatus::Stop,
+            ExitStatus::Return(vec![0x01]),
+            ExitStatus::Suicide,
+            ExitStatus::Revert(vec![]),
+        ];
+
+        for status in statuses {
+            let code = status.code().unwrap();
+            assert_eq!(ExitStatus::status_by_code(code), Some(status.status()));
+        }
+
+        assert_eq!(ExitStatus::StepLimit.code(), None);
+        assert_eq!(ExitStatus::status_by_code(0x00), None);
+    }
+
+    const ORIGIN: Address = Address([0x01; 20]);
+    const CONTRACT: Address = Address([0xaa; 20]);
+    const CALLEE: Address = Address([0xcc; 20]);
+
+    // PUSH1 0, PUSH1 0, LOG0
+    const LOG0: [u8; 5] = [0x60, 0x00, 0x60, 0x00, 0xa0];
+
+    /// CALL(0, address, 0, 0, 0, 0, 0), POP
+    fn call(address: &Address) -> Vec<u8> {
+        let mut code = vec![0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x73];
+        code.extend_from_slice(address.as_bytes());
+        code.extend_from_slice(&[0x60, 0x00, 0xf1, 0x50]);
+        code
+    }
+
+    async fn run(
+        backend: &mut TestDatabase,
+        mut trx: Transaction,
+        configure: impl FnOnce(&mut Machine<TestDatabase>),
+    ) -> Result<ExitStatus> {
+        let mut evm = Machine::new(&mut trx, ORIGIN, backend, None).await?;
+        configure(&mut evm);
+
+        let (status, _) = evm.execute(u64::MAX, backend).await?;
+        Ok(status)
+    }
+
+    #[tokio::test]
+    async fn logs_limit_exceeded() {
+        let mut backend = TestDatabase::default();
+        // JUMPDEST, LOG0, PUSH1 0, JUMP
+        let code = [&[0x5b][..], &LOG0, &[0x60, 0x00, 0x56]].concat();
+        backend.set_code(CONTRACT, Buffer::from_slice(&code)).unwrap();
+
+        let trx = transaction(Some(CONTRACT), &[]);
+        let result = run(&mut backend, trx, |evm| evm.set_logs_limit(10)).await;
+        assert!(matches!(result, Err(Error::TooManyLogs(address)) if address == CONTRACT));
+    }
+
+    #[tokio::test]
+    async fn logs_of_reverted_call_are_not_counted() {
+        let mut backend = TestDatabase::default();
+
+        // LOG0 x3, REVERT(0, 0)
+        let callee_code = [&LOG0[..], &LOG0, &LOG0, &[0x60, 0x00, 0x60, 0x00, 0xfd]].concat();
+        backend.set_code(CALLEE, Buffer::from_slice(&callee_code)).unwrap();
+
+        // CALL(CALLEE), LOG0 x3, STOP
+        let code = [&call(&CALLEE)[..], &LOG0, &LOG0, &LOG0, &[0x00]].concat();
+        backend.set_code(CONTRACT, Buffer::from_slice(&code)).unwrap();
+
+        let trx = transaction(Some(CONTRACT), &[]);
+        let result = run(&mut backend, trx, |evm| evm.set_logs_limit(3)).await;
+        assert_eq!(result.unwrap(), ExitStatus::Stop);
+    }
+
+    #[tokio::test]
+    async fn call_to_denied_contract() {
+        let mut backend = TestDatabase::default();
+        let code = [&call(&CALLEE)[..], &[0x00]].concat();
+        backend.set_code(CONTRACT, Buffer::from_slice(&code)).unwrap();
+
+        let trx = transaction(Some(CONTRACT), &[]);
+        let result = run(&mut backend, trx, |evm| evm.set_denylist(&[CALLEE])).await;
+        assert!(matches!(result, Err(Error::AddressDenied(address)) if address == CALLEE));
+    }
+
+    #[tokio::test]
+    async fn accounts_limit_stops_execution() {
+        let mut backend = TestDatabase::default();
+        backend.set_accounts_limit(2);
+        // CREATE(0, 0, 0), POP, CREATE(0, 0, 0), POP, STOP
+        let create = [0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0xf0, 0x50];
+        let code = [&create[..], &create, &[0x00]].concat();
+        backend.set_code(CONTRACT, Buffer::from_slice(&code)).unwrap();
+
+        let trx = transaction(Some(CONTRACT), &[]);
+        let result = run(&mut backend, trx, |_| {}).await;
+        assert!(matches!(result, Err(Error::TooManyAccountsTouched(2))));
+    }
+
+    // RETURN(0, 1), deploys a single STOP
+    const INIT_CODE: [u8; 5] = [0x60, 0x01, 0x60, 0x00, 0xf3];
+
+    #[tokio::test]
+    async fn deployment_address() {
+        let mut backend = TestDatabase::default();
+
+        let trx = transaction(None, &INIT_CODE);
+        let result = run(&mut backend, trx, |_| {}).await;
+        assert!(matches!(result.unwrap(), ExitStatus::Return(_)));
+
+        // The address emulation reports as `created_address`
+        let created_address = Address::from_create(&ORIGIN, 0);
+        let code = backend.code(&created_address).await.unwrap();
+        assert_eq!(code.to_vec(), vec![0x00]);
+    }
+
+    #[tokio::test]
+    async fn create2_address() {
+        let mut backend = TestDatabase::default();
+        let mut salt = [0_u8; 32];
+        salt[31] = 0x2a;
+
+        // MSTORE(0, INIT_CODE), CREATE2(0, 27, 5, salt), POP, STOP
+        let code = [
+            &[0x64][..],
+            &INIT_CODE,
+            &[0x60, 0x00, 0x52],
+            &[0x60, 0x2a, 0x60, 0x05, 0x60, 0x1b, 0x60, 0x00, 0xf5, 0x50, 0x00],
+        ]
+        .concat();
+        backend.set_code(CONTRACT, Buffer::from_slice(&code)).unwrap();
+
+        let trx = transaction(Some(CONTRACT), &[]);
+        let result = run(&mut backend, trx, |_| {}).await;
+        assert_eq!(result.unwrap(), ExitStatus::Stop);
+
+        // The address `prepare_deploy` predicts for the salt
+        let created_address = Address::from_create2(&CONTRACT, &salt, &INIT_CODE);
+        let code = backend.code(&created_address).await.unwrap();
+        assert_eq!(code.to_vec(), vec![0x00]);
+    }
+
+    #[tokio::test]
+    async fn call_stack_limit() {
+        let mut backend = TestDatabase::default();
+        // SSTORE(0, SLOAD(0) + 1), CALL(0, ADDRESS, 0, 0, 0, 0, 0), STOP
+        let code = [
+            0x60, 0x00, 0x54, 0x60, 0x01, 0x01, 0x60, 0x00, 0x55, 0x60, 0x00, 0x60, 0x00, 0x60,
+            0x00, 0x60, 0x00, 0x60, 0x00, 0x30, 0x60, 0x00, 0xf1, 0x00,
+        ];
+        backend.set_code(CONTRACT, Buffer::from_slice(&code)).unwrap();
+
+        let trx = transaction(Some(CONTRACT), &[]);
+        let result = run(&mut backend, trx, |_| {}).await;
+        assert_eq!(result.unwrap(), ExitStatus::Stop);
+
+        // The transaction frame and CALL_STACK_LIMIT nested ones, the next call fails
+        let calls = backend.storage(&CONTRACT, &U256::ZERO).await.unwrap();
+        assert_eq!(U256::from_be_bytes(calls).as_usize(), CALL_STACK_LIMIT + 1);
+    }
+
+    #[tokio::test]
+    async fn create_stack_limit() {
+        let mut backend = TestDatabase::default();
+        // CODECOPY(0, 0, CODESIZE), CREATE(0, 0, CODESIZE), STOP
+        let init_code = [
+            0x38, 0x60, 0x00, 0x60, 0x00, 0x39, 0x38, 0x60, 0x00, 0x60, 0x00, 0xf0, 0x00,
+        ];
+
+        let trx = transaction(None, &init_code);
+        let result = run(&mut backend, trx, |_| {}).await;
+        assert_eq!(result.unwrap(), ExitStatus::Stop);
+
+        // The origin, the deployed contract and CREATE_STACK_LIMIT nested ones,
+        // the next creation fails long before CALL_STACK_LIMIT is reached
+        assert_eq!(backend.accounts_with_nonce(), CREATE_STACK_LIMIT + 2);
+    }
 }
@@ -963,6 +963,14 @@ impl<B: Database> Machine<B> {
             return Err(Error::StaticModeViolation(self.context.contract));
         }
 
+        #[cfg(not(target_os = "solana"))]
+        if let Some(logs_left) = &mut self.logs_left {
+            let Some(left) = logs_left.checked_sub(1) else {
+                return Err(Error::TooManyLogs(self.context.contract));
+            };
+            *logs_left = left;
+        }
+
         let offset = self.stack.pop_usize()?;
         let length = self.stack.pop_usize()?;
 
@@ -1062,7 +1070,8 @@ impl<B: Database> Machine<B> {
             self,
             super::tracing::Event::BeginVM {
                 context,
-                code: init_code.to_vec()
+                code: init_code.to_vec(),
+                input: Vec::new()
             }
         );
 
@@ -1070,6 +1079,7 @@ impl<B: Database> Machine<B> {
         backend.snapshot();
 
         sol_log_data(&[b"ENTER", b"CREATE", address.as_bytes()]);
+        self.check_call_depth()?;
 
         if (backend.nonce(&address).await? != 0) || (backend.code_size(&address).await? != 0) {
             return Err(Error::DeployToExistingAccount(address, self.context.caller));
@@ -1098,6 +1108,9 @@ impl<B: Database> Machine<B> {
         let return_offset = self.stack.pop_usize()?;
         let return_length = self.stack.pop_usize()?;
 
+        #[cfg(not(target_os = "solana"))]
+        self.check_denylist(&address)?;
+
         self.return_data = Buffer::empty();
         self.return_range = return_offset..(return_offset + return_length);
 
@@ -1115,7 +1128,8 @@ impl<B: Database> Machine<B> {
             self,
             super::tracing::Event::BeginVM {
                 context,
-                code: code.to_vec()
+                code: code.to_vec(),
+                input: call_data.to_vec()
             }
         );
 
@@ -1123,6 +1137,7 @@ impl<B: Database> Machine<B> {
         backend.snapshot();
 
         sol_log_data(&[b"ENTER", b"CALL", address.as_bytes()]);
+        self.check_call_depth()?;
 
         if self.is_static && (value != U256::ZERO) {
             return Err(Error::StaticModeViolation(self.context.caller));
@@ -1150,6 +1165,9 @@ impl<B: Database> Machine<B> {
         let return_offset = self.stack.pop_usize()?;
         let return_length = self.stack.pop_usize()?;
 
+        #[cfg(not(target_os = "solana"))]
+        self.check_denylist(&address)?;
+
         self.return_data = Buffer::empty();
         self.return_range = return_offset..(return_offset + return_length);
 
@@ -1167,7 +1185,8 @@ impl<B: Database> Machine<B> {
             self,
             super::tracing::Event::BeginVM {
                 context,
-                code: code.to_vec()
+                code: code.to_vec(),
+                input: call_data.to_vec()
             }
         );
 
@@ -1175,6 +1194,7 @@ impl<B: Database> Machine<B> {
         backend.snapshot();
 
         sol_log_data(&[b"ENTER", b"CALLCODE", address.as_bytes()]);
+        self.check_call_depth()?;
 
         if backend.balance(&self.context.caller).await? < value {
             return Err(Error::InsufficientBalance(self.context.caller, value));
@@ -1194,6 +1214,9 @@ impl<B: Database> Machine<B> {
         let return_offset = self.stack.pop_usize()?;
         let return_length = self.stack.pop_usize()?;
 
+        #[cfg(not(target_os = "solana"))]
+        self.check_denylist(&address)?;
+
         self.return_data = Buffer::empty();
         self.return_range = return_offset..(return_offset + return_length);
 
@@ -1209,7 +1232,8 @@ impl<B: Database> Machine<B> {
             self,
             super::tracing::Event::BeginVM {
                 context,
-                code: code.to_vec()
+                code: code.to_vec(),
+                input: call_data.to_vec()
             }
         );
 
@@ -1217,6 +1241,7 @@ impl<B: Database> Machine<B> {
         backend.snapshot();
 
         sol_log_data(&[b"ENTER", b"DELEGATECALL", address.as_bytes()]);
+        self.check_call_depth()?;
 
         self.opcode_call_precompile_impl(backend, &address).await
     }
@@ -1232,6 +1257,9 @@ impl<B: Database> Machine<B> {
         let return_offset = self.stack.pop_usize()?;
         let return_length = self.stack.pop_usize()?;
 
+        #[cfg(not(target_os = "solana"))]
+        self.check_denylist(&address)?;
+
         self.return_data = Buffer::empty();
         self.return_range = return_offset..(return_offset + return_length);
 
@@ -1249,7 +1277,8 @@ impl<B: Database> Machine<B> {
             self,
             super::tracing::Event::BeginVM {
                 context,
-                code: code.to_vec()
+                code: code.to_vec(),
+                input: call_data.to_vec()
             }
         );
 
@@ -1259,6 +1288,7 @@ impl<B: Database> Machine<B> {
         backend.snapshot();
 
         sol_log_data(&[b"ENTER", b"STATICCALL", address.as_bytes()]);
+        self.check_call_depth()?;
 
         self.opcode_call_precompile_impl(backend, &address).await
     }
@@ -1327,7 +1357,7 @@ impl<B: Database> Machine<B> {
             }
         );
 
-        let returned = self.join();
+        let returned = self.join_committed();
         match returned.reason {
             Reason::Call => {
                 self.memory.write_range(&self.return_range, &return_data)?;
@@ -1431,7 +1461,7 @@ impl<B: Database> Machine<B> {
             }
         );
 
-        let returned = self.join();
+        let returned = self.join_committed();
         match returned.reason {
             Reason::Call => {
                 self.memory.write_range(&self.return_range, &[])?;
@@ -1463,7 +1493,7 @@ impl<B: Database> Machine<B> {
             }
         );
 
-        let returned = self.join();
+        let returned = self.join_committed();
         match returned.reason {
             Reason::Call => {
                 self.memory.write_range(&self.return_range, &[])?;
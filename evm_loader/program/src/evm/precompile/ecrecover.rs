@@ -1,7 +1,7 @@
 use arrayref::{array_ref, array_refs};
 use ethnum::U256;
-use solana_program::keccak;
-use solana_program::secp256k1_recover::secp256k1_recover;
+
+use crate::types::recover_sender;
 
 #[allow(clippy::manual_let_else)]
 #[must_use]
@@ -24,13 +24,15 @@ pub fn ecrecover(input: &[u8]) -> Vec<u8> {
         return vec![];
     }
 
-    let recovery_id = v.as_u8() - 27;
+    let mut signature = [0_u8; 65];
+    signature[0..64].copy_from_slice(sig);
+    signature[64] = v.as_u8() - 27;
 
-    let Ok(public_key) = secp256k1_recover(&msg[..], recovery_id, &sig[..]) else { return vec![] };
+    let Ok(address) = recover_sender(&signature, msg) else { return vec![] };
 
-    let mut address = keccak::hash(&public_key.to_bytes()).to_bytes();
-    address[0..12].fill(0);
+    let mut result = vec![0_u8; 32];
+    result[12..].copy_from_slice(address.as_bytes());
 
-    debug_print!("{}", hex::encode(address));
-    address.to_vec()
+    debug_print!("{}", hex::encode(&result));
+    result
 }
@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+
+use ethnum::U256;
+use maybe_async::maybe_async;
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+
+use super::{database::Database, Buffer, Context};
+use crate::{
+    error::{Error, Result},
+    types::{Address, LegacyTx, Transaction, TransactionPayload},
+};
+
+#[derive(Clone, Default)]
+struct Account {
+    nonce: u64,
+    balance: U256,
+    code: Vec<u8>,
+    storage: HashMap<U256, [u8; 32]>,
+}
+
+/// In-memory `Database` for running the interpreter in tests
+#[derive(Default)]
+pub struct TestDatabase {
+    accounts: HashMap<Address, Account>,
+    snapshots: Vec<HashMap<Address, Account>>,
+    accounts_limit: Option<usize>,
+}
+
+impl TestDatabase {
+    /// Report the accounts limit as exceeded once more than `limit` accounts are stored
+    pub fn set_accounts_limit(&mut self, limit: usize) {
+        self.accounts_limit = Some(limit);
+    }
+
+    /// Number of accounts with a non-zero nonce
+    #[must_use]
+    pub fn accounts_with_nonce(&self) -> usize {
+        self.accounts.values().filter(|account| account.nonce > 0).count()
+    }
+}
+
+/// Transaction from an account with zero nonce, gas is free
+#[must_use]
+pub fn transaction(target: Option<Address>, call_data: &[u8]) -> Transaction {
+    Transaction {
+        transaction: TransactionPayload::Legacy(LegacyTx {
+            nonce: 0,
+            gas_price: U256::ZERO,
+            gas_limit: U256::MAX,
+            target,
+            value: U256::ZERO,
+            call_data: Buffer::from_slice(call_data),
+            v: U256::ZERO,
+            r: U256::ZERO,
+            s: U256::ZERO,
+            chain_id: None,
+            recovery_id: 0,
+        }),
+        byte_len: 0,
+        hash: [0; 32],
+        signed_hash: [0; 32],
+    }
+}
+
+#[maybe_async(?Send)]
+impl Database for TestDatabase {
+    fn chain_id(&self) -> U256 {
+        U256::new(111)
+    }
+
+    async fn nonce(&self, address: &Address) -> Result<u64> {
+        Ok(self.accounts.get(address).map_or(0, |a| a.nonce))
+    }
+
+    fn increment_nonce(&mut self, address: Address) -> Result<()> {
+        let account = self.accounts.entry(address).or_default();
+        account.nonce = account.nonce.checked_add(1).ok_or(Error::IntegerOverflow)?;
+        Ok(())
+    }
+
+    async fn balance(&self, address: &Address) -> Result<U256> {
+        Ok(self.accounts.get(address).map_or(U256::ZERO, |a| a.balance))
+    }
+
+    async fn transfer(&mut self, source: Address, target: Address, value: U256) -> Result<()> {
+        if value == U256::ZERO {
+            return Ok(());
+        }
+
+        let source_account = self.accounts.entry(source).or_default();
+        source_account.balance = source_account
+            .balance
+            .checked_sub(value)
+            .ok_or(Error::InsufficientBalance(source, value))?;
+
+        let target_account = self.accounts.entry(target).or_default();
+        target_account.balance += value;
+
+        Ok(())
+    }
+
+    async fn code_size(&self, address: &Address) -> Result<usize> {
+        Ok(self.accounts.get(address).map_or(0, |a| a.code.len()))
+    }
+
+    async fn code_hash(&self, address: &Address) -> Result<[u8; 32]> {
+        let code = self.accounts.get(address).map(|a| a.code.as_slice());
+        Ok(solana_program::keccak::hash(code.unwrap_or_default()).to_bytes())
+    }
+
+    async fn code(&self, address: &Address) -> Result<Buffer> {
+        let code = self.accounts.get(address).map(|a| a.code.as_slice());
+        Ok(Buffer::from_slice(code.unwrap_or_default()))
+    }
+
+    fn set_code(&mut self, address: Address, code: Buffer) -> Result<()> {
+        self.accounts.entry(address).or_default().code = code.to_vec();
+        Ok(())
+    }
+
+    fn selfdestruct(&mut self, address: Address) -> Result<()> {
+        self.accounts.remove(&address);
+        Ok(())
+    }
+
+    async fn storage(&self, address: &Address, index: &U256) -> Result<[u8; 32]> {
+        let value = self
+            .accounts
+            .get(address)
+            .and_then(|account| account.storage.get(index));
+
+        Ok(value.copied().unwrap_or_default())
+    }
+
+    fn set_storage(&mut self, address: Address, index: U256, value: [u8; 32]) -> Result<()> {
+        let account = self.accounts.entry(address).or_default();
+        account.storage.insert(index, value);
+        Ok(())
+    }
+
+    async fn block_hash(&self, _number: U256) -> Result<[u8; 32]> {
+        Ok([0; 32])
+    }
+
+    fn block_number(&self) -> Result<U256> {
+        Ok(U256::ZERO)
+    }
+
+    fn block_timestamp(&self) -> Result<U256> {
+        Ok(U256::ZERO)
+    }
+
+    /// Solana accounts are not stored, every one is mapped as a nonexistent account
+    async fn map_solana_account<F, R>(&self, address: &Pubkey, action: F) -> R
+    where
+        F: FnOnce(&AccountInfo) -> R,
+    {
+        let mut lamports = 0_u64;
+        let mut data: [u8; 0] = [];
+        let owner = solana_program::system_program::ID;
+        let info = AccountInfo::new(
+            address,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+
+        action(&info)
+    }
+
+    fn snapshot(&mut self) {
+        self.snapshots.push(self.accounts.clone());
+    }
+
+    fn revert_snapshot(&mut self) {
+        self.accounts = self.snapshots.pop().expect("snapshot to revert");
+    }
+
+    fn commit_snapshot(&mut self) {
+        self.snapshots.pop().expect("snapshot to commit");
+    }
+
+    async fn precompile_extension(
+        &mut self,
+        _context: &Context,
+        _address: &Address,
+        _data: &[u8],
+        _is_static: bool,
+    ) -> Option<Result<Vec<u8>>> {
+        None
+    }
+
+    fn exceeded_accounts_limit(&self) -> Option<usize> {
+        self.accounts_limit
+            .filter(|&limit| self.accounts.len() > limit)
+    }
+}
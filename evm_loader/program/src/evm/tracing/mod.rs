@@ -1,5 +1,5 @@
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Debug;
 use std::rc::Rc;
 
@@ -20,6 +20,24 @@ pub struct EmulationResult {
     pub steps_executed: u64,
     pub used_gas: u64,
     pub actions: Vec<Action>,
+    pub state_diff: Option<StateDiff>,
+}
+
+/// State of an account before or after the transaction
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountState {
+    pub balance: U256,
+    pub nonce: u64,
+    pub code: Vec<u8>,
+    /// Only the storage slots modified by the transaction
+    pub storage: BTreeMap<U256, [u8; 32]>,
+}
+
+/// Accounts touched by the transaction. Self destructed accounts are missing from `post`
+#[derive(Debug, Clone, Default)]
+pub struct StateDiff {
+    pub pre: BTreeMap<Address, AccountState>,
+    pub post: BTreeMap<Address, AccountState>,
 }
 
 pub trait EventListener: Send + Sync + Debug {
@@ -35,6 +53,7 @@ pub enum Event {
     BeginVM {
         context: Context,
         code: Vec<u8>,
+        input: Vec<u8>,
     },
     EndVM {
         status: ExitStatus,
@@ -0,0 +1,365 @@
+use ethnum::U256;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::format_revert_error;
+use crate::evm::opcode_table::OPNAMES;
+use crate::evm::tracing::TraceConfig;
+use crate::evm::tracing::{EmulationResult, Event, EventListener};
+use crate::evm::ExitStatus;
+use crate::types::hexbytes::HexBytes;
+use crate::types::Address;
+
+const OPCODE_CALL: u8 = 0xF1;
+const OPCODE_CALLCODE: u8 = 0xF2;
+const OPCODE_DELEGATECALL: u8 = 0xF4;
+const OPCODE_STATICCALL: u8 = 0xFA;
+const OPCODE_CREATE: u8 = 0xF0;
+const OPCODE_CREATE2: u8 = 0xF5;
+
+/// `CallFrame` describes a single call or contract creation
+/// see <https://github.com/ethereum/go-ethereum/blob/master/eth/tracers/native/call.go#L59>
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+    #[serde(rename = "type")]
+    type_: &'static str,
+    from: Address,
+    to: Address,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<U256>,
+    /// Neon EVM does not meter gas per call frame,
+    /// so only the top level frame reports gas
+    gas: U256,
+    gas_used: U256,
+    input: HexBytes,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output: Option<HexBytes>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    revert_reason: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    calls: Vec<CallFrame>,
+}
+
+impl CallFrame {
+    fn exit(&mut self, status: &ExitStatus) {
+        match status {
+            ExitStatus::Return(output) => {
+                if !output.is_empty() {
+                    self.output = Some(output.clone().into());
+                }
+            }
+            ExitStatus::Revert(output) => {
+                self.error = Some("execution reverted".to_string());
+                self.revert_reason = format_revert_error(output).map(str::to_string);
+                if !output.is_empty() {
+                    self.output = Some(output.clone().into());
+                }
+            }
+            ExitStatus::StepLimit => {
+                self.error = Some(status.status().to_string());
+            }
+            ExitStatus::Stop | ExitStatus::Suicide => {}
+        }
+    }
+}
+
+/// See <https://github.com/ethereum/go-ethereum/blob/master/eth/tracers/native/call.go#L110>
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Config {
+    #[serde(default)]
+    only_top_call: bool,
+}
+
+impl From<&TraceConfig> for Config {
+    fn from(trace_config: &TraceConfig) -> Self {
+        trace_config
+            .tracer_config
+            .clone()
+            .and_then(|config| serde_json::from_value(config).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Geth-style `callTracer`, builds a tree of the call frames entered during execution
+#[derive(Debug)]
+pub struct CallTracer {
+    config: Config,
+    frames: Vec<CallFrame>,
+    root: Option<CallFrame>,
+    skipped_depth: usize,
+    last_opcode: Option<u8>,
+}
+
+impl CallTracer {
+    #[must_use]
+    pub fn new(trace_config: &TraceConfig) -> Self {
+        CallTracer {
+            config: trace_config.into(),
+            frames: vec![],
+            root: None,
+            skipped_depth: 0,
+            last_opcode: None,
+        }
+    }
+
+    fn close_frame(&mut self, status: &ExitStatus) {
+        let Some(mut frame) = self.frames.pop() else {
+            return;
+        };
+
+        frame.exit(status);
+
+        if let Some(parent) = self.frames.last_mut() {
+            parent.calls.push(frame);
+            return;
+        }
+
+        self.root = Some(frame);
+    }
+}
+
+impl EventListener for CallTracer {
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::BeginVM {
+                context,
+                code,
+                input,
+            } => {
+                if self.config.only_top_call && !self.frames.is_empty() {
+                    self.skipped_depth += 1;
+                    return;
+                }
+
+                // The top level frame is not entered by an opcode
+                let opcode = self.last_opcode.take().unwrap_or_else(|| {
+                    if context.code_address.is_none() {
+                        OPCODE_CREATE
+                    } else {
+                        OPCODE_CALL
+                    }
+                });
+
+                let (from, input) = match opcode {
+                    OPCODE_CREATE | OPCODE_CREATE2 => (context.caller, code),
+                    OPCODE_DELEGATECALL => (context.contract, input),
+                    _ => (context.caller, input),
+                };
+
+                let value = match opcode {
+                    OPCODE_DELEGATECALL | OPCODE_STATICCALL => None,
+                    _ => Some(context.value),
+                };
+
+                self.frames.push(CallFrame {
+                    type_: OPNAMES[opcode as usize],
+                    from,
+                    to: context.code_address.unwrap_or(context.contract),
+                    value,
+                    gas: U256::ZERO,
+                    gas_used: U256::ZERO,
+                    input: input.into(),
+                    output: None,
+                    error: None,
+                    revert_reason: None,
+                    calls: vec![],
+                });
+            }
+            Event::EndVM { status } => {
+                if self.skipped_depth > 0 {
+                    self.skipped_depth -= 1;
+                    return;
+                }
+
+                self.close_frame(&status);
+            }
+            Event::BeginStep { opcode, .. } => {
+                self.last_opcode = match opcode {
+                    OPCODE_CALL | OPCODE_CALLCODE | OPCODE_DELEGATECALL | OPCODE_STATICCALL
+                    | OPCODE_CREATE | OPCODE_CREATE2 => Some(opcode),
+                    _ => None,
+                };
+            }
+            Event::EndStep { .. } | Event::StorageAccess { .. } => {}
+        };
+    }
+
+    fn into_traces(mut self: Box<Self>, emulation_result: EmulationResult) -> Value {
+        // Frames left open when the emulation stopped on the step limit
+        while !self.frames.is_empty() {
+            self.close_frame(&ExitStatus::StepLimit);
+        }
+
+        let Some(mut root) = self.root else {
+            return Value::Null;
+        };
+
+        root.gas = U256::from(emulation_result.used_gas);
+        root.gas_used = U256::from(emulation_result.used_gas);
+
+        serde_json::to_value(root).expect("Conversion error")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::build_revert_message;
+    use crate::evm::Context;
+
+    fn address(byte: u8) -> Address {
+        Address([byte; 20])
+    }
+
+    fn begin_vm(caller: u8, contract: u8, code_address: Option<u8>) -> Event {
+        Event::BeginVM {
+            context: Context {
+                caller: address(caller),
+                contract: address(contract),
+                value: U256::ZERO,
+                code_address: code_address.map(address),
+            },
+            code: vec![0x60, 0x00],
+            input: vec![0xAA],
+        }
+    }
+
+    fn begin_step(opcode: u8) -> Event {
+        Event::BeginStep {
+            opcode,
+            pc: 0,
+            stack: vec![],
+            memory: vec![],
+        }
+    }
+
+    fn emulation_result(exit_status: ExitStatus) -> EmulationResult {
+        EmulationResult {
+            exit_status,
+            steps_executed: 10,
+            used_gas: 25_000,
+            actions: vec![],
+            state_diff: None,
+        }
+    }
+
+    fn trace_nested_call(trace_config: &TraceConfig) -> Value {
+        let mut tracer = Box::new(CallTracer::new(trace_config));
+        let revert = build_revert_message("nested failure");
+
+        tracer.event(begin_vm(1, 2, Some(2)));
+        tracer.event(begin_step(OPCODE_STATICCALL));
+        tracer.event(begin_vm(2, 3, Some(3)));
+        tracer.event(Event::EndVM {
+            status: ExitStatus::Revert(revert),
+        });
+        tracer.event(begin_step(OPCODE_CREATE));
+        tracer.event(begin_vm(2, 4, None));
+        tracer.event(Event::EndVM {
+            status: ExitStatus::Return(vec![]),
+        });
+        tracer.event(Event::EndVM {
+            status: ExitStatus::Return(vec![0x01]),
+        });
+
+        tracer.into_traces(emulation_result(ExitStatus::Return(vec![0x01])))
+    }
+
+    #[test]
+    fn test_nested_calls() {
+        let trace = trace_nested_call(&TraceConfig::default());
+
+        assert_eq!(trace["type"], "CALL");
+        assert_eq!(trace["from"], address(1).to_string());
+        assert_eq!(trace["to"], address(2).to_string());
+        assert_eq!(trace["gasUsed"], "0x61a8");
+        assert_eq!(trace["output"], "0x01");
+
+        let calls = trace["calls"].as_array().unwrap();
+        assert_eq!(calls.len(), 2);
+
+        assert_eq!(calls[0]["type"], "STATICCALL");
+        assert_eq!(calls[0]["input"], "0xaa");
+        assert_eq!(calls[0]["error"], "execution reverted");
+        assert_eq!(calls[0]["revertReason"], "nested failure");
+        assert!(calls[0].get("value").is_none());
+
+        assert_eq!(calls[1]["type"], "CREATE");
+        assert_eq!(calls[1]["to"], address(4).to_string());
+        assert_eq!(calls[1]["input"], "0x6000");
+        assert!(calls[1].get("output").is_none());
+    }
+
+    #[test]
+    fn test_only_top_call() {
+        let trace_config = TraceConfig {
+            tracer_config: Some(serde_json::json!({ "onlyTopCall": true })),
+            ..TraceConfig::default()
+        };
+        let trace = trace_nested_call(&trace_config);
+
+        assert_eq!(trace["type"], "CALL");
+        assert_eq!(trace["output"], "0x01");
+        assert!(trace.get("calls").is_none());
+    }
+
+    #[test]
+    fn test_step_limit_closes_open_frames() {
+        let mut tracer = Box::new(CallTracer::new(&TraceConfig::default()));
+
+        tracer.event(begin_vm(1, 2, Some(2)));
+        tracer.event(begin_step(OPCODE_DELEGATECALL));
+        tracer.event(begin_vm(1, 2, Some(3)));
+        tracer.event(Event::EndVM {
+            status: ExitStatus::StepLimit,
+        });
+
+        let trace = tracer.into_traces(emulation_result(ExitStatus::StepLimit));
+
+        assert_eq!(trace["error"], "step limit exceeded");
+        let calls = trace["calls"].as_array().unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0]["type"], "DELEGATECALL");
+        assert_eq!(calls[0]["from"], address(2).to_string());
+        assert_eq!(calls[0]["to"], address(3).to_string());
+    }
+
+    #[test]
+    fn test_value_transfer() {
+        let mut tracer = Box::new(CallTracer::new(&TraceConfig::default()));
+
+        tracer.event(Event::BeginVM {
+            context: Context {
+                caller: address(1),
+                contract: address(2),
+                value: U256::new(1_000),
+                code_address: Some(address(2)),
+            },
+            code: vec![],
+            input: vec![],
+        });
+        tracer.event(begin_step(0x00));
+        tracer.event(Event::EndVM {
+            status: ExitStatus::Stop,
+        });
+
+        let trace = tracer.into_traces(emulation_result(ExitStatus::Stop));
+
+        assert_eq!(
+            trace,
+            serde_json::json!({
+                "type": "CALL",
+                "from": address(1).to_string(),
+                "to": address(2).to_string(),
+                "value": "0x3e8",
+                "gas": "0x61a8",
+                "gasUsed": "0x61a8",
+                "input": "0x"
+            })
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::evm::tracing::{EmulationResult, Event, EventListener};
+
+/// Geth-style `4byteTracer`, counts the selectors and call data sizes of the call frames
+/// see <https://github.com/ethereum/go-ethereum/blob/master/eth/tracers/native/4byte.go>
+#[derive(Debug, Default)]
+pub struct FourByteTracer {
+    ids: BTreeMap<String, u64>,
+}
+
+impl FourByteTracer {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl EventListener for FourByteTracer {
+    fn event(&mut self, event: Event) {
+        let Event::BeginVM { context, input, .. } = event else {
+            return;
+        };
+
+        // Contract creation has no selector
+        if context.code_address.is_none() {
+            return;
+        }
+
+        if input.len() < 4 {
+            return;
+        }
+
+        let id = format!("0x{}-{}", hex::encode(&input[..4]), input.len() - 4);
+        *self.ids.entry(id).or_insert(0) += 1;
+    }
+
+    fn into_traces(self: Box<Self>, _emulation_result: EmulationResult) -> Value {
+        serde_json::to_value(self.ids).expect("Conversion error")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evm::{Context, ExitStatus};
+    use crate::types::Address;
+    use ethnum::U256;
+
+    fn begin_vm(code_address: Option<Address>, input: Vec<u8>) -> Event {
+        Event::BeginVM {
+            context: Context {
+                caller: Address([1; 20]),
+                contract: Address([2; 20]),
+                value: U256::ZERO,
+                code_address,
+            },
+            code: vec![],
+            input,
+        }
+    }
+
+    #[test]
+    fn test_selector_counts() {
+        let mut tracer = Box::new(FourByteTracer::new());
+        let contract = Some(Address([2; 20]));
+
+        tracer.event(begin_vm(contract, vec![0xa9, 0x05, 0x9c, 0xbb, 0x00, 0x01]));
+        tracer.event(begin_vm(contract, vec![0xa9, 0x05, 0x9c, 0xbb, 0x00, 0x02]));
+        tracer.event(begin_vm(contract, vec![0x70, 0xa0, 0x82, 0x31]));
+        tracer.event(begin_vm(contract, vec![0x01, 0x02]));
+        tracer.event(begin_vm(None, vec![0x60, 0x80, 0x60, 0x40, 0x52]));
+
+        let trace = tracer.into_traces(EmulationResult {
+            exit_status: ExitStatus::Stop,
+            steps_executed: 1,
+            used_gas: 0,
+            actions: vec![],
+            state_diff: None,
+        });
+
+        assert_eq!(
+            trace,
+            serde_json::json!({ "0xa9059cbb-2": 2, "0x70a08231-0": 1 })
+        );
+    }
+}
@@ -1,15 +1,24 @@
+use crate::evm::tracing::tracers::call_tracer::CallTracer;
+use crate::evm::tracing::tracers::four_byte_tracer::FourByteTracer;
+use crate::evm::tracing::tracers::prestate_tracer::PrestateTracer;
 use crate::evm::tracing::tracers::struct_logger::StructLogger;
 use crate::evm::tracing::TraceConfig;
 use crate::evm::tracing::TracerType;
 use std::cell::RefCell;
 use std::rc::Rc;
 
+pub mod call_tracer;
+pub mod four_byte_tracer;
+pub mod prestate_tracer;
 pub mod struct_logger;
 
 pub fn new_tracer(trace_config: &TraceConfig) -> crate::error::Result<TracerType> {
     Ok(Rc::new(RefCell::new(
         match trace_config.tracer.as_deref() {
             None | Some("") => Box::new(StructLogger::new(trace_config)),
+            Some("callTracer") => Box::new(CallTracer::new(trace_config)),
+            Some("prestateTracer") => Box::new(PrestateTracer::new(trace_config)),
+            Some("4byteTracer") => Box::new(FourByteTracer::new()),
             _ => {
                 return Err(crate::error::Error::Custom(format!(
                     "Unsupported tracer: {:?}",
@@ -19,3 +28,28 @@ pub fn new_tracer(trace_config: &TraceConfig) -> crate::error::Result<TracerType
         },
     )))
 }
+
+/// Only `prestateTracer` reports the state of the touched accounts,
+/// which takes extra reads of every account touched by the transaction
+#[must_use]
+pub fn needs_state_diff(trace_config: &TraceConfig) -> bool {
+    trace_config.tracer.as_deref() == Some("prestateTracer")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_diff_is_collected_for_prestate_tracer_only() {
+        let config = |tracer: Option<&str>| TraceConfig {
+            tracer: tracer.map(str::to_string),
+            ..TraceConfig::default()
+        };
+
+        assert!(needs_state_diff(&config(Some("prestateTracer"))));
+        assert!(!needs_state_diff(&config(Some("callTracer"))));
+        assert!(!needs_state_diff(&config(Some("4byteTracer"))));
+        assert!(!needs_state_diff(&config(None)));
+    }
+}
@@ -0,0 +1,360 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use ethnum::U256;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::evm::tracing::TraceConfig;
+use crate::evm::tracing::{AccountState, EmulationResult, Event, EventListener, StateDiff};
+use crate::types::hexbytes::HexBytes;
+use crate::types::Address;
+
+const OPCODE_SLOAD: u8 = 0x54;
+const OPCODE_SSTORE: u8 = 0x55;
+
+type Storage = BTreeMap<U256, [u8; 32]>;
+
+fn hex_word(word: &[u8; 32]) -> String {
+    format!("0x{}", hex::encode(word))
+}
+
+/// `PrestateAccount` is the state of an account as reported by the `prestateTracer`
+/// see <https://github.com/ethereum/go-ethereum/blob/master/eth/tracers/native/prestate.go#L44>
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct PrestateAccount {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    balance: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nonce: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<HexBytes>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    storage: BTreeMap<String, String>,
+}
+
+impl PrestateAccount {
+    /// Accounts which do not exist are reported as an empty object
+    fn new<'a>(
+        state: &AccountState,
+        storage: impl Iterator<Item = (&'a U256, &'a [u8; 32])>,
+    ) -> Self {
+        let exists = (state.balance != 0) || (state.nonce != 0) || !state.code.is_empty();
+
+        Self {
+            balance: exists.then_some(state.balance),
+            nonce: (state.nonce != 0).then_some(state.nonce),
+            code: (!state.code.is_empty()).then(|| state.code.clone().into()),
+            storage: storage
+                .map(|(index, value)| (hex_word(&index.to_be_bytes()), hex_word(value)))
+                .collect(),
+        }
+    }
+
+    /// Only the fields changed between `pre` and `post`
+    fn changes(pre: &AccountState, post: &AccountState) -> Self {
+        Self {
+            balance: (pre.balance != post.balance).then_some(post.balance),
+            nonce: (pre.nonce != post.nonce).then_some(post.nonce),
+            code: (pre.code != post.code).then(|| post.code.clone().into()),
+            storage: modified_slots(pre, post)
+                .map(|(index, value)| (hex_word(&index.to_be_bytes()), hex_word(value)))
+                .collect(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.balance.is_none()
+            && self.nonce.is_none()
+            && self.code.is_none()
+            && self.storage.is_empty()
+    }
+}
+
+/// Storage slots of `state` which have a different value in `base`
+fn modified_slots<'a>(
+    base: &'a AccountState,
+    state: &'a AccountState,
+) -> impl Iterator<Item = (&'a U256, &'a [u8; 32])> {
+    state
+        .storage
+        .iter()
+        .filter(|(index, value)| base.storage.get(index) != Some(value))
+}
+
+/// See <https://github.com/ethereum/go-ethereum/blob/master/eth/tracers/native/prestate.go#L70>
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Config {
+    #[serde(default)]
+    diff_mode: bool,
+}
+
+impl From<&TraceConfig> for Config {
+    fn from(trace_config: &TraceConfig) -> Self {
+        trace_config
+            .tracer_config
+            .clone()
+            .and_then(|config| serde_json::from_value(config).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Geth-style `prestateTracer`, reports the state of the accounts touched by the transaction
+#[derive(Debug)]
+pub struct PrestateTracer {
+    config: Config,
+    contracts: Vec<Address>,
+    last_opcode: u8,
+    /// First value read by SLOAD from slots not written before the read
+    storage_reads: BTreeMap<Address, Storage>,
+    storage_writes: BTreeSet<(Address, U256)>,
+}
+
+impl PrestateTracer {
+    #[must_use]
+    pub fn new(trace_config: &TraceConfig) -> Self {
+        PrestateTracer {
+            config: trace_config.into(),
+            contracts: vec![],
+            last_opcode: 0,
+            storage_reads: BTreeMap::new(),
+            storage_writes: BTreeSet::new(),
+        }
+    }
+
+    fn prestate(self, state_diff: &StateDiff) -> BTreeMap<Address, PrestateAccount> {
+        let mut storage_reads = self.storage_reads;
+        let mut result = BTreeMap::new();
+
+        for (address, state) in &state_diff.pre {
+            let reads = storage_reads.remove(address).unwrap_or_default();
+            let storage = state.storage.iter().chain(&reads);
+
+            result.insert(*address, PrestateAccount::new(state, storage));
+        }
+
+        for (address, reads) in &storage_reads {
+            let state = AccountState::default();
+            result.insert(*address, PrestateAccount::new(&state, reads.iter()));
+        }
+
+        result
+    }
+
+    fn diff(state_diff: &StateDiff) -> Value {
+        let mut pre = BTreeMap::new();
+        let mut post = BTreeMap::new();
+
+        for (address, pre_state) in &state_diff.pre {
+            let Some(post_state) = state_diff.post.get(address) else {
+                // Self destructed
+                let storage = pre_state.storage.iter();
+                pre.insert(*address, PrestateAccount::new(pre_state, storage));
+                continue;
+            };
+
+            let changes = PrestateAccount::changes(pre_state, post_state);
+            if changes.is_empty() {
+                continue;
+            }
+
+            let storage = modified_slots(post_state, pre_state);
+            pre.insert(*address, PrestateAccount::new(pre_state, storage));
+            post.insert(*address, changes);
+        }
+
+        serde_json::json!({ "pre": pre, "post": post })
+    }
+}
+
+impl EventListener for PrestateTracer {
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::BeginVM { context, .. } => {
+                self.contracts.push(context.contract);
+            }
+            Event::EndVM { .. } => {
+                self.contracts.pop();
+            }
+            Event::BeginStep { opcode, .. } => {
+                self.last_opcode = opcode;
+            }
+            Event::StorageAccess { index, value } => {
+                let Some(&address) = self.contracts.last() else {
+                    return;
+                };
+
+                match self.last_opcode {
+                    OPCODE_SLOAD if !self.storage_writes.contains(&(address, index)) => {
+                        self.storage_reads
+                            .entry(address)
+                            .or_default()
+                            .entry(index)
+                            .or_insert(value);
+                    }
+                    OPCODE_SSTORE => {
+                        self.storage_writes.insert((address, index));
+                    }
+                    _ => {}
+                }
+            }
+            Event::EndStep { .. } => {}
+        };
+    }
+
+    fn into_traces(self: Box<Self>, emulation_result: EmulationResult) -> Value {
+        let state_diff = emulation_result.state_diff.unwrap_or_default();
+
+        if self.config.diff_mode {
+            return Self::diff(&state_diff);
+        }
+
+        serde_json::to_value(self.prestate(&state_diff)).expect("Conversion error")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evm::{Context, ExitStatus};
+
+    fn address(byte: u8) -> Address {
+        Address([byte; 20])
+    }
+
+    fn word(byte: u8) -> [u8; 32] {
+        let mut word = [0_u8; 32];
+        word[31] = byte;
+        word
+    }
+
+    fn state_diff() -> StateDiff {
+        let sender = AccountState {
+            balance: U256::new(1000),
+            nonce: 1,
+            ..AccountState::default()
+        };
+        let contract = AccountState {
+            balance: U256::ZERO,
+            nonce: 1,
+            code: vec![0x60, 0x00],
+            storage: BTreeMap::from([(U256::new(2), word(5))]),
+        };
+
+        let mut pre = BTreeMap::new();
+        pre.insert(address(1), sender.clone());
+        pre.insert(address(2), contract.clone());
+        pre.insert(address(3), AccountState::default());
+
+        let mut post = BTreeMap::new();
+        post.insert(
+            address(1),
+            AccountState {
+                balance: U256::new(900),
+                nonce: 2,
+                ..sender
+            },
+        );
+        post.insert(
+            address(2),
+            AccountState {
+                storage: BTreeMap::from([(U256::new(2), word(6))]),
+                ..contract
+            },
+        );
+        post.insert(
+            address(3),
+            AccountState {
+                balance: U256::new(100),
+                nonce: 1,
+                code: vec![0x00],
+                storage: BTreeMap::new(),
+            },
+        );
+
+        StateDiff { pre, post }
+    }
+
+    fn trace(trace_config: &TraceConfig) -> Value {
+        let mut tracer = Box::new(PrestateTracer::new(trace_config));
+
+        tracer.event(Event::BeginVM {
+            context: Context {
+                caller: address(1),
+                contract: address(2),
+                value: U256::ZERO,
+                code_address: Some(address(2)),
+            },
+            code: vec![0x60, 0x00],
+            input: vec![],
+        });
+        for (opcode, index, value) in [
+            (OPCODE_SLOAD, 1, 7),
+            (OPCODE_SSTORE, 2, 6),
+            (OPCODE_SLOAD, 2, 6),
+        ] {
+            tracer.event(Event::BeginStep {
+                opcode,
+                pc: 0,
+                stack: vec![],
+                memory: vec![],
+            });
+            tracer.event(Event::StorageAccess {
+                index: U256::new(index),
+                value: word(value),
+            });
+        }
+        tracer.event(Event::EndVM {
+            status: ExitStatus::Stop,
+        });
+
+        tracer.into_traces(EmulationResult {
+            exit_status: ExitStatus::Stop,
+            steps_executed: 10,
+            used_gas: 0,
+            actions: vec![],
+            state_diff: Some(state_diff()),
+        })
+    }
+
+    #[test]
+    fn test_prestate() {
+        let trace = trace(&TraceConfig::default());
+
+        let sender = &trace[address(1).to_string()];
+        assert_eq!(sender["balance"], "0x3e8");
+        assert_eq!(sender["nonce"], 1);
+
+        let contract = &trace[address(2).to_string()];
+        assert_eq!(contract["code"], "0x6000");
+        assert_eq!(contract["storage"][hex_word(&word(1))], hex_word(&word(7)));
+        assert_eq!(contract["storage"][hex_word(&word(2))], hex_word(&word(5)));
+
+        assert_eq!(trace[address(3).to_string()], serde_json::json!({}));
+    }
+
+    #[test]
+    fn test_diff_mode() {
+        let trace_config = TraceConfig {
+            tracer_config: Some(serde_json::json!({ "diffMode": true })),
+            ..TraceConfig::default()
+        };
+        let trace = trace(&trace_config);
+
+        let pre = &trace["pre"];
+        let post = &trace["post"];
+
+        assert_eq!(pre[address(1).to_string()]["balance"], "0x3e8");
+        assert_eq!(post[address(1).to_string()]["balance"], "0x384");
+        assert_eq!(post[address(1).to_string()]["nonce"], 2);
+
+        let contract = address(2).to_string();
+        assert!(post[&contract].get("code").is_none());
+        assert_eq!(pre[&contract]["storage"][hex_word(&word(2))], hex_word(&word(5)));
+        assert_eq!(post[&contract]["storage"][hex_word(&word(2))], hex_word(&word(6)));
+        assert!(pre[&contract]["storage"].get(hex_word(&word(1))).is_none());
+
+        assert_eq!(pre[address(3).to_string()], serde_json::json!({}));
+        assert_eq!(post[address(3).to_string()]["code"], "0x00");
+    }
+}
@@ -118,7 +118,8 @@ impl From<&TraceConfig> for Config {
 pub struct StructLogger {
     config: Config,
     logs: Vec<StructLog>,
-    depth: usize,
+    /// Entered call frames, `true` for frames without code
+    frames: Vec<bool>,
     storage_access: Option<(U256, U256)>,
 }
 
@@ -128,20 +129,25 @@ impl StructLogger {
         StructLogger {
             config: trace_config.into(),
             logs: vec![],
-            depth: 0,
+            frames: vec![],
             storage_access: None,
         }
     }
+
+    /// Calls to accounts without code only run the implicit `STOP`, which is not logged
+    fn in_empty_code(&self) -> bool {
+        self.frames.last().copied().unwrap_or(false)
+    }
 }
 
 impl EventListener for StructLogger {
     fn event(&mut self, event: Event) {
         match event {
-            Event::BeginVM { .. } => {
-                self.depth += 1;
+            Event::BeginVM { code, .. } => {
+                self.frames.push(code.is_empty());
             }
             Event::EndVM { .. } => {
-                self.depth -= 1;
+                self.frames.pop();
             }
             Event::BeginStep {
                 opcode,
@@ -149,6 +155,10 @@ impl EventListener for StructLogger {
                 stack,
                 memory,
             } => {
+                if self.in_empty_code() {
+                    return;
+                }
+
                 let stack = if self.config.disable_stack {
                     None
                 } else {
@@ -166,13 +176,18 @@ impl EventListener for StructLogger {
                     None
                 };
 
-                let log = StructLog::new(opcode, pc as u64, 0, self.depth, memory, stack);
+                let depth = self.frames.len();
+                let log = StructLog::new(opcode, pc as u64, 0, depth, memory, stack);
                 self.logs.push(log);
             }
             Event::EndStep {
                 gas_used,
                 return_data,
             } => {
+                if self.in_empty_code() {
+                    return;
+                }
+
                 let last = self
                     .logs
                     .last_mut()
@@ -221,6 +236,55 @@ impl EventListener for StructLogger {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::evm::{Context, ExitStatus};
+    use crate::types::Address;
+
+    #[test]
+    fn test_value_transfer_has_no_struct_logs() {
+        let mut tracer = Box::new(StructLogger::new(&TraceConfig::default()));
+
+        tracer.event(Event::BeginVM {
+            context: Context {
+                caller: Address([1; 20]),
+                contract: Address([2; 20]),
+                value: U256::new(1_000),
+                code_address: Some(Address([2; 20])),
+            },
+            code: vec![],
+            input: vec![],
+        });
+        tracer.event(Event::BeginStep {
+            opcode: 0x00,
+            pc: 0,
+            stack: vec![],
+            memory: vec![],
+        });
+        tracer.event(Event::EndStep {
+            gas_used: 0,
+            return_data: None,
+        });
+        tracer.event(Event::EndVM {
+            status: ExitStatus::Stop,
+        });
+
+        let trace = tracer.into_traces(EmulationResult {
+            exit_status: ExitStatus::Stop,
+            steps_executed: 1,
+            used_gas: 21_000,
+            actions: vec![],
+            state_diff: None,
+        });
+
+        assert_eq!(
+            trace,
+            serde_json::json!({
+                "gas": 21_000,
+                "failed": false,
+                "returnValue": "",
+                "structLogs": []
+            })
+        );
+    }
 
     #[test]
     fn test_serialize_struct_logger_result_all_fields() {
@@ -0,0 +1,135 @@
+use std::convert::TryInto;
+
+use ethnum::U256;
+use maybe_async::maybe_async;
+use solana_program::keccak::{hash, Hash};
+
+use crate::{
+    account_storage::AccountStorage,
+    error::{Error, Result},
+    executor::ExecutorState,
+    types::Address,
+};
+
+use super::read_usize;
+
+// Keccak method ids:
+//-------------------------------------------
+// keccak256Batch(bytes[]) => 0x52feda40
+//-------------------------------------------
+
+#[maybe_async]
+pub async fn keccak<B: AccountStorage>(
+    _state: &mut ExecutorState<'_, B>,
+    address: &Address,
+    input: &[u8],
+    context: &crate::evm::Context,
+    _is_static: bool,
+) -> Result<Vec<u8>> {
+    debug_print!("keccak({})", hex::encode(input));
+
+    if context.value != 0 {
+        return Err(Error::Custom("Keccak: value != 0".to_string()));
+    }
+
+    if input.len() < 4 {
+        return Err(Error::OutOfBounds);
+    }
+
+    let (method_id, rest) = input.split_at(4);
+    let method_id: [u8; 4] = method_id.try_into()?;
+
+    match method_id {
+        [0x52, 0xfe, 0xda, 0x40] => {
+            debug_print!("keccak.keccak256Batch()");
+            keccak256_batch(rest)
+        }
+        _ => {
+            debug_print!("keccak UNKNOWN {:?}", method_id);
+            Err(Error::UnknownPrecompileMethodSelector(*address, method_id))
+        }
+    }
+}
+
+/// Hashes every element of ABI encoded `bytes[]`, returns ABI encoded `bytes32[]`
+fn keccak256_batch(input: &[u8]) -> Result<Vec<u8>> {
+    let array_offset = read_usize(input, 0)?;
+    let count = read_usize(input, array_offset)?;
+
+    let elements = array_offset + 32;
+    let elements_input = input.get(elements..).ok_or(Error::OutOfBounds)?;
+    if count > elements_input.len() / 32 {
+        return Err(Error::OutOfBounds);
+    }
+
+    let mut result = vec![0_u8; 32 + 32 + count * 32];
+    result[31] = 0x20; // offset
+    result[32..64].copy_from_slice(&U256::new(count as u128).to_be_bytes());
+
+    for i in 0..count {
+        let offset = read_usize(elements_input, i * 32)?;
+        let length = read_usize(elements_input, offset)?;
+
+        let begin = offset + 32;
+        let data = begin
+            .checked_add(length)
+            .and_then(|end| elements_input.get(begin..end))
+            .ok_or(Error::OutOfBounds)?;
+
+        let Hash(digest) = hash(data);
+        result[64 + i * 32..96 + i * 32].copy_from_slice(&digest);
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(value: usize) -> [u8; 32] {
+        U256::new(value as u128).to_be_bytes()
+    }
+
+    #[test]
+    fn keccak256_batch_digests() {
+        // bytes[] ["abc", ""]
+        let mut input = vec![];
+        input.extend_from_slice(&word(0x20));
+        input.extend_from_slice(&word(2));
+        input.extend_from_slice(&word(0x40));
+        input.extend_from_slice(&word(0x80));
+        input.extend_from_slice(&word(3));
+        input.extend_from_slice(b"abc");
+        input.extend_from_slice(&[0_u8; 29]);
+        input.extend_from_slice(&word(0));
+
+        let result = keccak256_batch(&input).unwrap();
+
+        assert_eq!(result.len(), 32 + 32 + 2 * 32);
+        assert_eq!(&result[0..32], &word(0x20));
+        assert_eq!(&result[32..64], &word(2));
+        assert_eq!(
+            hex::encode(&result[64..96]),
+            "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"
+        );
+        assert_eq!(
+            hex::encode(&result[96..128]),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+    }
+
+    #[test]
+    fn keccak256_batch_malformed() {
+        // Element length runs past the end of the input
+        let mut input = vec![];
+        input.extend_from_slice(&word(0x20));
+        input.extend_from_slice(&word(1));
+        input.extend_from_slice(&word(0x20));
+        input.extend_from_slice(&word(64));
+        input.extend_from_slice(&[0_u8; 32]);
+
+        assert!(keccak256_batch(&input).is_err());
+        assert!(keccak256_batch(&word(0x20)).is_err());
+    }
+}
@@ -19,7 +19,10 @@ use crate::{
 };
 
 // "[0xc5, 0x73, 0x50, 0xc6]": "createMetadata(bytes32,string,string,string)"
+// "[0x5d, 0x41, 0x72, 0xf9]": "createMetadata(bytes32,string,string,string,bool)"
 // "[0x4a, 0xe8, 0xb6, 0x6b]": "createMasterEdition(bytes32,uint64)"
+// "[0x89, 0xf3, 0x5d, 0xd1]": "verifyCollection(bytes32,bytes32)"
+// "[0x8d, 0x11, 0x18, 0xc1]": "setAndVerifyCollection(bytes32,bytes32)"
 // "[0xf7, 0xb6, 0x37, 0xbb]": "isInitialized(bytes32)"
 // "[0x23, 0x5b, 0x2b, 0x94]": "isNFT(bytes32)"
 // "[0x9e, 0xd1, 0x9d, 0xdb]": "uri(bytes32)"
@@ -44,6 +47,10 @@ pub async fn metaplex<B: AccountStorage>(
         ));
     }
 
+    if input.len() < 4 {
+        return Err(Error::OutOfBounds);
+    }
+
     let (selector, input) = input.split_at(4);
     let selector: [u8; 4] = selector.try_into()?;
 
@@ -59,7 +66,25 @@ pub async fn metaplex<B: AccountStorage>(
             let symbol = read_string(input, 64, 256)?;
             let uri = read_string(input, 96, 1024)?;
 
-            create_metadata(context, state, mint, name, symbol, uri)
+            create_metadata(context, state, mint, name, symbol, uri, false)
+        }
+        [0x5d, 0x41, 0x72, 0xf9] => {
+            // "createMetadata(bytes32,string,string,string,bool)"
+            if is_static {
+                return Err(Error::StaticModeViolation(*address));
+            }
+
+            if input.len() < 160 {
+                return Err(Error::OutOfBounds);
+            }
+
+            let mint = read_pubkey(input)?;
+            let name = read_string(input, 32, 256)?;
+            let symbol = read_string(input, 64, 256)?;
+            let uri = read_string(input, 96, 1024)?;
+            let is_mutable = read_bool(&input[128..])?;
+
+            create_metadata(context, state, mint, name, symbol, uri, is_mutable)
         }
         [0x4a, 0xe8, 0xb6, 0x6b] => {
             // "createMasterEdition(bytes32,uint64)"
@@ -67,11 +92,45 @@ pub async fn metaplex<B: AccountStorage>(
                 return Err(Error::StaticModeViolation(*address));
             }
 
+            if input.len() < 64 {
+                return Err(Error::OutOfBounds);
+            }
+
             let mint = read_pubkey(input)?;
             let max_supply = read_u64(&input[32..])?;
 
             create_master_edition(context, state, mint, Some(max_supply))
         }
+        [0x89, 0xf3, 0x5d, 0xd1] => {
+            // "verifyCollection(bytes32,bytes32)"
+            if is_static {
+                return Err(Error::StaticModeViolation(*address));
+            }
+
+            if input.len() < 64 {
+                return Err(Error::OutOfBounds);
+            }
+
+            let mint = read_pubkey(input)?;
+            let collection_mint = read_pubkey(&input[32..])?;
+
+            verify_collection(context, state, mint, collection_mint, false).await
+        }
+        [0x8d, 0x11, 0x18, 0xc1] => {
+            // "setAndVerifyCollection(bytes32,bytes32)"
+            if is_static {
+                return Err(Error::StaticModeViolation(*address));
+            }
+
+            if input.len() < 64 {
+                return Err(Error::OutOfBounds);
+            }
+
+            let mint = read_pubkey(input)?;
+            let collection_mint = read_pubkey(&input[32..])?;
+
+            verify_collection(context, state, mint, collection_mint, true).await
+        }
         [0xf7, 0xb6, 0x37, 0xbb] => {
             // "isInitialized(bytes32)"
             let mint = read_pubkey(input)?;
@@ -111,6 +170,18 @@ fn read_u64(input: &[u8]) -> Result<u64> {
         .map_err(Into::into)
 }
 
+#[inline]
+fn read_bool(input: &[u8]) -> Result<bool> {
+    if input.len() < 32 {
+        return Err(Error::OutOfBounds);
+    }
+    match U256::from_be_bytes(*arrayref::array_ref![input, 0, 32]) {
+        U256::ZERO => Ok(false),
+        U256::ONE => Ok(true),
+        _ => Err(Error::Custom("Invalid bool value".to_string())),
+    }
+}
+
 #[inline]
 fn read_pubkey(input: &[u8]) -> Result<Pubkey> {
     if input.len() < 32 {
@@ -151,6 +222,7 @@ fn create_metadata<B: AccountStorage>(
     name: String,
     symbol: String,
     uri: String,
+    is_mutable: bool,
 ) -> Result<Vec<u8>> {
     let signer = context.caller;
     let (signer_pubkey, bump_seed) = state.backend.solana_address(&signer);
@@ -185,12 +257,12 @@ fn create_metadata<B: AccountStorage>(
                 share: 100,
             },
         ]),
-        0,     // Seller Fee
-        true,  // Update Authority == Mint Authority
-        false, // Is Mutable
-        None,  // Collection
-        None,  // Uses
-        None,  // Collection Details
+        0,          // Seller Fee
+        true,       // Update Authority == Mint Authority
+        is_mutable, // Is Mutable
+        None,       // Collection
+        None,       // Uses
+        None,       // Collection Details
     );
 
     let rent = Rent::get()?;
@@ -238,6 +310,79 @@ fn create_master_edition<B: AccountStorage>(
     Ok(edition_pubkey.to_bytes().to_vec())
 }
 
+#[maybe_async]
+async fn verify_collection<B: AccountStorage>(
+    context: &crate::evm::Context,
+    state: &mut ExecutorState<'_, B>,
+    mint: Pubkey,
+    collection_mint: Pubkey,
+    set_collection: bool,
+) -> Result<Vec<u8>> {
+    let signer = context.caller;
+    let (signer_pubkey, bump_seed) = state.backend.solana_address(&signer);
+
+    // Only the update authority of the collection may verify items
+    let is_collection_authority = metadata(context, state, collection_mint)
+        .await?
+        .map_or(false, |m| m.update_authority == signer_pubkey);
+    if !is_collection_authority {
+        return Ok(to_solidity_bool(false));
+    }
+
+    let Some(item) = metadata(context, state, mint).await? else {
+        return Ok(to_solidity_bool(false));
+    };
+
+    if set_collection {
+        // Setting the collection also requires the item update authority
+        if item.update_authority != signer_pubkey {
+            return Ok(to_solidity_bool(false));
+        }
+    } else if item.collection.map(|c| c.key) != Some(collection_mint) {
+        return Ok(to_solidity_bool(false));
+    }
+
+    let seeds = vec![
+        vec![ACCOUNT_SEED_VERSION],
+        signer.as_bytes().to_vec(),
+        vec![bump_seed],
+    ];
+
+    let (metadata_pubkey, _) = mpl_token_metadata::pda::find_metadata_account(&mint);
+    let (collection_pubkey, _) = mpl_token_metadata::pda::find_metadata_account(&collection_mint);
+    let (collection_edition_pubkey, _) =
+        mpl_token_metadata::pda::find_master_edition_account(&collection_mint);
+
+    let instruction = if set_collection {
+        mpl_token_metadata::instruction::set_and_verify_collection(
+            mpl_token_metadata::ID,
+            metadata_pubkey,
+            signer_pubkey,
+            *state.backend.operator(),
+            signer_pubkey,
+            collection_mint,
+            collection_pubkey,
+            collection_edition_pubkey,
+            None,
+        )
+    } else {
+        mpl_token_metadata::instruction::verify_collection(
+            mpl_token_metadata::ID,
+            metadata_pubkey,
+            signer_pubkey,
+            *state.backend.operator(),
+            collection_mint,
+            collection_pubkey,
+            collection_edition_pubkey,
+            None,
+        )
+    };
+
+    state.queue_external_instruction(instruction, seeds, 0);
+
+    Ok(to_solidity_bool(true))
+}
+
 #[maybe_async]
 async fn is_initialized<B: AccountStorage>(
     context: &crate::evm::Context,
@@ -336,11 +481,7 @@ fn to_solidity_string(s: &str) -> Vec<u8> {
     // 32 bytes - length
     // length + padding bytes - data
 
-    let data_len = if s.len() % 32 == 0 {
-        std::cmp::max(s.len(), 32)
-    } else {
-        ((s.len() / 32) + 1) * 32
-    };
+    let data_len = (s.len() + 31) & !31; // round up to 32 bytes, empty string has no data
 
     let mut result = vec![0_u8; 32 + 32 + data_len];
 
@@ -353,3 +494,124 @@ fn to_solidity_string(s: &str) -> Vec<u8> {
 
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{account_storage::test_storage::TestAccountStorage, evm::Context};
+
+    const METAPLEX: Address = Address([
+        0xff, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x05,
+    ]);
+
+    async fn call(storage: &TestAccountStorage, input: &[u8], is_static: bool) -> Result<Vec<u8>> {
+        let context = Context {
+            caller: Address([0x01; 20]),
+            contract: METAPLEX,
+            value: U256::ZERO,
+            code_address: None,
+        };
+
+        let mut state = ExecutorState::new(storage);
+        metaplex(&mut state, &METAPLEX, input, &context, is_static).await
+    }
+
+    fn word(value: usize) -> [u8; 32] {
+        U256::new(value as u128).to_be_bytes()
+    }
+
+    fn create_metadata_input(selector: [u8; 4], is_mutable: Option<usize>) -> Vec<u8> {
+        let head_len = if is_mutable.is_some() { 160 } else { 128 };
+
+        let mut input = selector.to_vec();
+        input.extend_from_slice(&[1; 32]);
+
+        let mut tail = Vec::new();
+        for s in ["name", "symbol", "uri"] {
+            input.extend_from_slice(&word(head_len + tail.len()));
+            tail.extend_from_slice(&to_solidity_string(s)[32..]);
+        }
+        if let Some(is_mutable) = is_mutable {
+            input.extend_from_slice(&word(is_mutable));
+        }
+
+        input.extend(tail);
+        input
+    }
+
+    #[test]
+    fn solidity_string_padding() {
+        for (len, encoded_len) in [(0, 64), (31, 96), (32, 96), (33, 128), (64, 128)] {
+            let s = "a".repeat(len);
+            let encoded = to_solidity_string(&s);
+
+            assert_eq!(encoded.len(), encoded_len, "string length {len}");
+            assert_eq!(encoded[31], 0x20);
+            assert_eq!(U256::from_be_bytes(*arrayref::array_ref![encoded, 32, 32]), len as u128);
+            assert_eq!(&encoded[64..64 + len], s.as_bytes());
+            assert!(encoded[64 + len..].iter().all(|b| *b == 0));
+        }
+    }
+
+    #[tokio::test]
+    async fn short_input() {
+        let storage = TestAccountStorage::default();
+        let create_master_edition = [0x4a, 0xe8, 0xb6, 0x6b];
+
+        for input in [&[][..], &[0x4a, 0xe8][..], &create_master_edition[..]] {
+            let result = call(&storage, input, false).await;
+            assert!(matches!(result, Err(Error::OutOfBounds)));
+        }
+
+        let mut input = create_master_edition.to_vec();
+        input.resize(4 + 63, 0);
+        let result = call(&storage, &input, false).await;
+        assert!(matches!(result, Err(Error::OutOfBounds)));
+    }
+
+    #[tokio::test]
+    async fn verify_collection_input() {
+        let storage = TestAccountStorage::default();
+        let mint = Pubkey::new_from_array([1; 32]);
+        let collection_mint = Pubkey::new_from_array([2; 32]);
+
+        for selector in [[0x89, 0xf3, 0x5d, 0xd1], [0x8d, 0x11, 0x18, 0xc1]] {
+            let input = [&selector[..], mint.as_ref(), collection_mint.as_ref()].concat();
+
+            // Neither mint has metadata, so nothing is verified
+            let result = call(&storage, &input, false).await.unwrap();
+            assert_eq!(result, to_solidity_bool(false));
+
+            let result = call(&storage, &input, true).await;
+            assert!(matches!(result, Err(Error::StaticModeViolation(_))));
+
+            let result = call(&storage, &input[..input.len() - 1], false).await;
+            assert!(matches!(result, Err(Error::OutOfBounds)));
+        }
+    }
+
+    #[test]
+    fn read_abi_bool() {
+        assert!(!read_bool(&word(0)).unwrap());
+        assert!(read_bool(&word(1)).unwrap());
+        assert!(matches!(read_bool(&word(2)), Err(Error::Custom(_))));
+        assert!(matches!(read_bool(&[0; 31]), Err(Error::OutOfBounds)));
+    }
+
+    #[tokio::test]
+    async fn create_metadata_with_flag_input() {
+        let storage = TestAccountStorage::default();
+        let with_flag = [0x5d, 0x41, 0x72, 0xf9];
+
+        let input = create_metadata_input(with_flag, Some(2));
+        let result = call(&storage, &input, false).await;
+        assert!(matches!(result, Err(Error::Custom(_))));
+
+        let input = create_metadata_input(with_flag, Some(1));
+        let result = call(&storage, &input[..4 + 159], false).await;
+        assert!(matches!(result, Err(Error::OutOfBounds)));
+
+        let result = call(&storage, &input, true).await;
+        assert!(matches!(result, Err(Error::StaticModeViolation(_))));
+    }
+}
@@ -1,11 +1,23 @@
-use crate::{account_storage::AccountStorage, error::Result, evm::Context, types::Address};
+use std::convert::TryInto;
+
+use arrayref::array_ref;
+use ethnum::U256;
 use maybe_async::maybe_async;
 
+use crate::{
+    account_storage::AccountStorage,
+    error::{Error, Result},
+    evm::Context,
+    types::Address,
+};
+
 use super::ExecutorState;
 
+mod keccak;
 mod metaplex;
 mod neon_token;
 mod query_account;
+mod solana_account;
 mod spl_token;
 
 impl<'a, B: AccountStorage> ExecutorState<'a, B> {
@@ -25,6 +37,12 @@ impl<'a, B: AccountStorage> ExecutorState<'a, B> {
     const SYSTEM_ACCOUNT_METAPLEX: Address = Address([
         0xff, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x05,
     ]);
+    const SYSTEM_ACCOUNT_KECCAK: Address = Address([
+        0xff, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x06,
+    ]);
+    const SYSTEM_ACCOUNT_SOLANA_ACCOUNT: Address = Address([
+        0xff, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x08,
+    ]);
 
     #[must_use]
     #[allow(clippy::unused_self)]
@@ -33,6 +51,8 @@ impl<'a, B: AccountStorage> ExecutorState<'a, B> {
             || *address == Self::SYSTEM_ACCOUNT_NEON_TOKEN
             || *address == Self::SYSTEM_ACCOUNT_SPL_TOKEN
             || *address == Self::SYSTEM_ACCOUNT_METAPLEX
+            || *address == Self::SYSTEM_ACCOUNT_KECCAK
+            || *address == Self::SYSTEM_ACCOUNT_SOLANA_ACCOUNT
     }
 
     #[maybe_async]
@@ -56,7 +76,25 @@ impl<'a, B: AccountStorage> ExecutorState<'a, B> {
             Self::SYSTEM_ACCOUNT_METAPLEX => {
                 Some(metaplex::metaplex(self, address, input, context, is_static).await)
             }
+            Self::SYSTEM_ACCOUNT_KECCAK => {
+                Some(keccak::keccak(self, address, input, context, is_static).await)
+            }
+            Self::SYSTEM_ACCOUNT_SOLANA_ACCOUNT => Some(
+                solana_account::solana_account(self, address, input, context, is_static).await,
+            ),
             _ => None,
         }
     }
 }
+
+/// Reads an ABI encoded `uint256` at `offset` which must fit into `usize`
+fn read_usize(input: &[u8], offset: usize) -> Result<usize> {
+    let end = offset.checked_add(32).ok_or(Error::OutOfBounds)?;
+    if input.len() < end {
+        return Err(Error::OutOfBounds);
+    }
+
+    let value = U256::from_be_bytes(*array_ref![input, offset, 32]);
+    let value: usize = value.try_into()?;
+    Ok(value)
+}
@@ -1,14 +1,16 @@
 use std::convert::TryInto;
 
 use arrayref::{array_ref, array_refs};
-use ethnum::U256;
+use ethnum::{I256, U256};
 use maybe_async::maybe_async;
 use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
 
 use crate::{
     account_storage::AccountStorage,
+    config::QUERY_ACCOUNT_DATA_MAX_LENGTH,
     error::{Error, Result},
-    executor::ExecutorState,
+    evm::database::Database,
+    executor::{ExecutorState, OwnedAccountInfo},
     types::Address,
 };
 
@@ -30,6 +32,10 @@ use crate::{
 // "b64a097e": "info(bytes32)",
 // "a9dbaf25": "length(bytes32)",
 // "7dd6c1a0": "data(bytes32,uint64,uint64)",
+// "4b96cd1c": "getAccountData(bytes32,uint64,uint64)",
+// "6a89d9dc": "solanaClock()",
+// "16279055": "isContract(address)",
+// "1e1e8e99": "contractChainId(address)",
 
 #[maybe_async]
 pub async fn query_account<B: AccountStorage>(
@@ -45,9 +51,34 @@ pub async fn query_account<B: AccountStorage>(
         return Err(Error::Custom("Query Account: value != 0".to_string()));
     }
 
+    if input.len() < 4 {
+        return Err(Error::OutOfBounds);
+    }
+
     let (method_id, rest) = input.split_at(4);
     let method_id: [u8; 4] = method_id.try_into()?;
 
+    if method_id == [0x6a, 0x89, 0xd9, 0xdc] {
+        debug_print!("query_account.solanaClock()");
+        return solana_clock(state);
+    }
+
+    if method_id == [0x16, 0x27, 0x90, 0x55] {
+        let contract = read_address(rest)?;
+        debug_print!("query_account.isContract({})", contract);
+        return is_contract(state, &contract).await;
+    }
+
+    if method_id == [0x1e, 0x1e, 0x8e, 0x99] {
+        let contract = read_address(rest)?;
+        debug_print!("query_account.contractChainId({})", contract);
+        return contract_chain_id(state, &contract).await;
+    }
+
+    if rest.len() < 32 {
+        return Err(Error::OutOfBounds);
+    }
+
     let (account_address, rest) = rest.split_at(32);
     let account_address = Pubkey::try_from(account_address)?;
 
@@ -78,10 +109,7 @@ pub async fn query_account<B: AccountStorage>(
             account_rent_epoch(state, &account_address).await
         }
         [0x43, 0xca, 0x51, 0x61] | [0x7d, 0xd6, 0xc1, 0xa0] => {
-            let arguments = array_ref![rest, 0, 64];
-            let (offset, length) = array_refs!(arguments, 32, 32);
-            let offset = U256::from_be_bytes(*offset).try_into()?;
-            let length = U256::from_be_bytes(*length).try_into()?;
+            let (offset, length) = read_offset_length(rest)?;
             debug_print!(
                 "query_account.data({}, {}, {})",
                 account_address,
@@ -90,6 +118,16 @@ pub async fn query_account<B: AccountStorage>(
             );
             account_data(state, &account_address, offset, length).await
         }
+        [0x4b, 0x96, 0xcd, 0x1c] => {
+            let (offset, length) = read_offset_length(rest)?;
+            debug_print!(
+                "query_account.getAccountData({}, {}, {})",
+                account_address,
+                offset,
+                length
+            );
+            get_account_data(state, account_address, offset, length).await
+        }
         [0xb6, 0x4a, 0x09, 0x7e] => {
             debug_print!("query_account.info({})", &account_address);
             account_info(state, &account_address).await
@@ -209,6 +247,143 @@ async fn account_data<B: AccountStorage>(
         .ok_or_else(|| Error::Custom("Query Account: data() - out of bounds".to_string()))
 }
 
+#[maybe_async]
+async fn get_account_data<B: AccountStorage>(
+    state: &mut ExecutorState<'_, B>,
+    address: Pubkey,
+    offset: usize,
+    length: usize,
+) -> Result<Vec<u8>> {
+    let account = external_account_data(state, address, length).await?;
+    let data = data_slice(&account.data, offset, length);
+
+    Ok(to_solidity_bytes(&data))
+}
+
+/// Loads the external account `length` bytes of data are read from,
+/// `length` is capped by `QUERY_ACCOUNT_DATA_MAX_LENGTH`
+#[maybe_async]
+pub(super) async fn external_account_data<B: AccountStorage>(
+    state: &ExecutorState<'_, B>,
+    address: Pubkey,
+    length: usize,
+) -> Result<OwnedAccountInfo> {
+    if length > QUERY_ACCOUNT_DATA_MAX_LENGTH {
+        return Err(Error::Custom(std::format!(
+            "Query Account: data length {length} exceeds {QUERY_ACCOUNT_DATA_MAX_LENGTH}"
+        )));
+    }
+
+    state.external_account(address).await
+}
+
+/// Slice of `data`, zero-padded where it extends beyond the end of the data
+pub(super) fn data_slice(data: &[u8], offset: usize, length: usize) -> Vec<u8> {
+    let mut result = vec![0_u8; length];
+
+    if let Some(available) = data.get(offset..) {
+        let copy_len = available.len().min(length);
+        result[..copy_len].copy_from_slice(&available[..copy_len]);
+    }
+
+    result
+}
+
+pub(super) fn to_solidity_bytes(data: &[u8]) -> Vec<u8> {
+    let data_len = (data.len() + 31) & !31; // round up to 32 bytes
+
+    let mut result = vec![0_u8; 32 + 32 + data_len];
+    result[31] = 0x20; // offset
+
+    let length = U256::new(data.len() as u128);
+    result[32..64].copy_from_slice(&length.to_be_bytes());
+    result[64..64 + data.len()].copy_from_slice(data);
+
+    result
+}
+
+fn read_address(input: &[u8]) -> Result<Address> {
+    if input.len() < 32 {
+        return Err(Error::OutOfBounds);
+    }
+
+    Ok(Address::from(*array_ref![input, 12, 20]))
+}
+
+/// `(uint64 offset, uint64 length)` arguments of the data methods
+pub(super) fn read_offset_length(input: &[u8]) -> Result<(usize, usize)> {
+    if input.len() < 64 {
+        return Err(Error::OutOfBounds);
+    }
+
+    let arguments = array_ref![input, 0, 64];
+    let (offset, length) = array_refs!(arguments, 32, 32);
+    let offset = U256::from_be_bytes(*offset).try_into()?;
+    let length = U256::from_be_bytes(*length).try_into()?;
+
+    Ok((offset, length))
+}
+
+#[maybe_async]
+async fn is_contract<B: AccountStorage>(
+    state: &mut ExecutorState<'_, B>,
+    address: &Address,
+) -> Result<Vec<u8>> {
+    let is_contract = is_deployed_contract(state, address).await?;
+
+    let mut result = vec![0_u8; 32];
+    result[31] = u8::from(is_contract);
+
+    Ok(result)
+}
+
+/// Every contract is bound to the chain id of the program,
+/// zero is returned for addresses without code
+#[maybe_async]
+async fn contract_chain_id<B: AccountStorage>(
+    state: &mut ExecutorState<'_, B>,
+    address: &Address,
+) -> Result<Vec<u8>> {
+    let is_contract = is_deployed_contract(state, address).await?;
+
+    Ok(to_solidity_chain_id(is_contract, state.chain_id()))
+}
+
+/// A contract has code and an existing contract account, unless it was deployed
+/// by this transaction. Contracts under construction have no code yet.
+#[maybe_async]
+async fn is_deployed_contract<B: AccountStorage>(
+    state: &ExecutorState<'_, B>,
+    address: &Address,
+) -> Result<bool> {
+    if state.is_precompile_extension(address) || (state.code_size(address).await? == 0) {
+        return Ok(false);
+    }
+
+    let deployed_by_transaction = state.backend.code_size(address).await == 0;
+    Ok(deployed_by_transaction || state.backend.exists(address).await)
+}
+
+fn to_solidity_chain_id(is_contract: bool, chain_id: U256) -> Vec<u8> {
+    let chain_id = if is_contract { chain_id } else { U256::ZERO };
+    chain_id.to_be_bytes().to_vec()
+}
+
+fn solana_clock<B: AccountStorage>(state: &ExecutorState<'_, B>) -> Result<Vec<u8>> {
+    let slot: u64 = state.block_number()?.try_into()?;
+    let unix_timestamp: i64 = state.block_timestamp()?.try_into()?;
+
+    Ok(to_solidity_clock(slot, unix_timestamp))
+}
+
+fn to_solidity_clock(slot: u64, unix_timestamp: i64) -> Vec<u8> {
+    let mut result = vec![0_u8; 64];
+    result[..32].copy_from_slice(&U256::from(slot).to_be_bytes());
+    result[32..].copy_from_slice(&I256::from(unix_timestamp).to_be_bytes());
+
+    result
+}
+
 #[allow(clippy::unnecessary_wraps)]
 #[maybe_async]
 async fn account_info<B: AccountStorage>(
@@ -236,3 +411,176 @@ async fn account_info<B: AccountStorage>(
 
     Ok(info)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        account_storage::test_storage::TestAccountStorage,
+        evm::{Buffer, Context},
+    };
+
+    const QUERY: Address = Address([
+        0xff, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x02,
+    ]);
+    const IS_CONTRACT: [u8; 4] = [0x16, 0x27, 0x90, 0x55];
+    const CONTRACT_CHAIN_ID: [u8; 4] = [0x1e, 0x1e, 0x8e, 0x99];
+    const GET_ACCOUNT_DATA: [u8; 4] = [0x4b, 0x96, 0xcd, 0x1c];
+
+    async fn query(
+        state: &mut ExecutorState<'_, TestAccountStorage>,
+        input: &[u8],
+    ) -> Result<Vec<u8>> {
+        let context = Context {
+            caller: Address::default(),
+            contract: QUERY,
+            value: U256::ZERO,
+            code_address: None,
+        };
+
+        query_account(state, &QUERY, input, &context, false).await
+    }
+
+    /// `selector(address)` call data
+    fn address_call(selector: [u8; 4], address: &Address) -> Vec<u8> {
+        [&selector[..], &[0; 12], address.as_bytes()].concat()
+    }
+
+    /// `getAccountData(bytes32,uint64,uint64)` call data
+    fn get_account_data_call(account: &Pubkey, offset: usize, length: usize) -> Vec<u8> {
+        let offset = U256::new(offset as u128).to_be_bytes();
+        let length = U256::new(length as u128).to_be_bytes();
+        [&GET_ACCOUNT_DATA[..], account.as_ref(), &offset, &length].concat()
+    }
+
+    #[tokio::test]
+    async fn get_account_data_of_mocked_account() {
+        let account = Pubkey::new_unique();
+        let mut storage = TestAccountStorage::default();
+        storage.add_solana_account(account, (1..=40).collect());
+        let mut state = ExecutorState::new(&storage);
+
+        let input = get_account_data_call(&account, 4, 8);
+        let result = query(&mut state, &input).await.unwrap();
+        assert_eq!(result.len(), 96);
+        assert_eq!(U256::from_be_bytes(*array_ref![result, 32, 32]), 8);
+        assert_eq!(&result[64..72], &[5, 6, 7, 8, 9, 10, 11, 12]);
+
+        // The part beyond the end of the data is zero-padded
+        let input = get_account_data_call(&account, 36, 8);
+        let result = query(&mut state, &input).await.unwrap();
+        assert_eq!(&result[64..72], &[37, 38, 39, 40, 0, 0, 0, 0]);
+
+        let input = get_account_data_call(&account, 100, 4);
+        let result = query(&mut state, &input).await.unwrap();
+        assert_eq!(U256::from_be_bytes(*array_ref![result, 32, 32]), 4);
+        assert!(result[64..].iter().all(|&b| b == 0));
+
+        let input = get_account_data_call(&account, 0, QUERY_ACCOUNT_DATA_MAX_LENGTH);
+        assert!(query(&mut state, &input).await.is_ok());
+
+        let input = get_account_data_call(&account, 0, QUERY_ACCOUNT_DATA_MAX_LENGTH + 1);
+        assert!(matches!(query(&mut state, &input).await, Err(Error::Custom(_))));
+    }
+
+    #[tokio::test]
+    async fn is_contract_by_account_kind() {
+        let eoa = Address([0x01; 20]);
+        let contract = Address([0x02; 20]);
+        let constructed = Address([0x03; 20]);
+        let deployed = Address([0x04; 20]);
+
+        let mut storage = TestAccountStorage::default();
+        storage.add_account(eoa, &[]);
+        storage.add_account(contract, &[0x00]);
+
+        // The constructor of `constructed` is running, `deployed` was created before it
+        let mut state = ExecutorState::new(&storage);
+        state.increment_nonce(constructed).unwrap();
+        state.set_code(deployed, Buffer::from_slice(&[0x00])).unwrap();
+
+        let cases = [(eoa, 0), (contract, 1), (constructed, 0), (deployed, 1), (QUERY, 0)];
+        for (address, expected) in cases {
+            let input = address_call(IS_CONTRACT, &address);
+            let result = query(&mut state, &input).await.unwrap();
+            assert_eq!(result.len(), 32);
+            assert_eq!(U256::from_be_bytes(*array_ref![result, 0, 32]), expected);
+        }
+    }
+
+    #[test]
+    fn get_account_data_slice() {
+        let account_data: Vec<u8> = (1..=40).collect();
+
+        let result = to_solidity_bytes(&data_slice(&account_data, 36, 8));
+        assert_eq!(result.len(), 96);
+        assert_eq!(U256::from_be_bytes(*array_ref![result, 0, 32]), 0x20);
+        assert_eq!(U256::from_be_bytes(*array_ref![result, 32, 32]), 8);
+        assert_eq!(&result[64..72], &[37, 38, 39, 40, 0, 0, 0, 0]);
+        assert!(result[72..].iter().all(|&b| b == 0));
+
+        assert_eq!(data_slice(&account_data, 100, 4), vec![0; 4]);
+    }
+
+    #[test]
+    fn read_abi_address() {
+        let mut input = [0_u8; 32];
+        input[12..].copy_from_slice(&[0xAB; 20]);
+        assert_eq!(read_address(&input).unwrap(), Address([0xAB; 20]));
+
+        assert!(read_address(&input[..31]).is_err());
+    }
+
+    #[test]
+    fn read_abi_offset_length() {
+        let mut input = [0_u8; 64];
+        input[31] = 8;
+        input[63] = 16;
+        assert_eq!(read_offset_length(&input).unwrap(), (8, 16));
+
+        assert!(matches!(
+            read_offset_length(&input[..63]),
+            Err(Error::OutOfBounds)
+        ));
+        assert!(read_offset_length(&[]).is_err());
+    }
+
+    #[test]
+    fn solana_clock_encoding() {
+        let result = to_solidity_clock(245_000_000, 1_700_000_000);
+        assert_eq!(U256::from_be_bytes(*array_ref![result, 0, 32]), 245_000_000);
+        assert_eq!(I256::from_be_bytes(*array_ref![result, 32, 32]), 1_700_000_000);
+
+        let result = to_solidity_clock(0, -1);
+        assert_eq!(&result[32..], &[0xFF; 32]);
+    }
+
+    #[tokio::test]
+    async fn contract_chain_id_of_contract() {
+        let eoa = Address([0x01; 20]);
+        let contract = Address([0x02; 20]);
+
+        let mut storage = TestAccountStorage::default();
+        storage.add_account(eoa, &[]);
+        storage.add_account(contract, &[0x00]);
+        let mut state = ExecutorState::new(&storage);
+
+        let input = address_call(CONTRACT_CHAIN_ID, &contract);
+        let result = query(&mut state, &input).await.unwrap();
+        assert_eq!(U256::from_be_bytes(*array_ref![result, 0, 32]), 111);
+
+        let input = address_call(CONTRACT_CHAIN_ID, &eoa);
+        let result = query(&mut state, &input).await.unwrap();
+        assert_eq!(result, vec![0_u8; 32]);
+    }
+
+    #[test]
+    fn contract_chain_id_encoding() {
+        let result = to_solidity_chain_id(true, U256::new(245_022_934));
+        assert_eq!(result.len(), 32);
+        assert_eq!(U256::from_be_bytes(*array_ref![result, 0, 32]), 245_022_934);
+
+        let result = to_solidity_chain_id(false, U256::new(245_022_934));
+        assert_eq!(result, vec![0_u8; 32]);
+    }
+}
@@ -0,0 +1,120 @@
+use std::convert::TryInto;
+
+use maybe_async::maybe_async;
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    account_storage::AccountStorage,
+    error::{Error, Result},
+    executor::{ExecutorState, OwnedAccountInfo},
+    types::Address,
+};
+
+use super::query_account::{
+    data_slice, external_account_data, read_offset_length, to_solidity_bytes,
+};
+
+// SolanaAccount method ids:
+//-------------------------------------------
+// readAccountData(bytes32,uint64,uint64) => 0x92a3acf9
+// accountOwner(bytes32)                  => 0x37955ab2
+//-------------------------------------------
+
+#[maybe_async]
+pub async fn solana_account<B: AccountStorage>(
+    state: &mut ExecutorState<'_, B>,
+    address: &Address,
+    input: &[u8],
+    context: &crate::evm::Context,
+    _is_static: bool,
+) -> Result<Vec<u8>> {
+    debug_print!("solana_account({})", hex::encode(input));
+
+    if context.value != 0 {
+        return Err(Error::Custom("Solana Account: value != 0".to_string()));
+    }
+
+    if input.len() < 4 + 32 {
+        return Err(Error::OutOfBounds);
+    }
+
+    let (method_id, rest) = input.split_at(4);
+    let method_id: [u8; 4] = method_id.try_into()?;
+
+    let (account_address, rest) = rest.split_at(32);
+    let account_address = Pubkey::try_from(account_address)?;
+
+    match method_id {
+        [0x92, 0xa3, 0xac, 0xf9] => {
+            let (offset, length) = read_offset_length(rest)?;
+            debug_print!(
+                "solana_account.readAccountData({}, {}, {})",
+                account_address,
+                offset,
+                length
+            );
+
+            let account = external_account_data(state, account_address, length).await?;
+            Ok(read_account_data(&account, offset, length))
+        }
+        [0x37, 0x95, 0x5a, 0xb2] => {
+            debug_print!("solana_account.accountOwner({})", account_address);
+
+            let account = state.external_account(account_address).await?;
+            Ok(account.owner.to_bytes().to_vec())
+        }
+        _ => {
+            debug_print!("solana_account UNKNOWN {:?}", method_id);
+            Err(Error::UnknownPrecompileMethodSelector(*address, method_id))
+        }
+    }
+}
+
+/// ABI encoded `bytes`, empty for accounts that don't exist
+fn read_account_data(account: &OwnedAccountInfo, offset: usize, length: usize) -> Vec<u8> {
+    if account.lamports == 0 {
+        return to_solidity_bytes(&[]);
+    }
+
+    to_solidity_bytes(&data_slice(&account.data, offset, length))
+}
+
+#[cfg(test)]
+mod tests {
+    use arrayref::array_ref;
+    use ethnum::U256;
+
+    use super::*;
+
+    fn account(lamports: u64, data: Vec<u8>) -> OwnedAccountInfo {
+        OwnedAccountInfo {
+            key: Pubkey::new_unique(),
+            is_signer: false,
+            is_writable: false,
+            lamports,
+            data,
+            owner: Pubkey::new_unique(),
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    #[test]
+    fn read_existing_account_data() {
+        let result = read_account_data(&account(1_000, (1..=8).collect()), 2, 4);
+
+        assert_eq!(result.len(), 96);
+        assert_eq!(U256::from_be_bytes(*array_ref![result, 0, 32]), 0x20);
+        assert_eq!(U256::from_be_bytes(*array_ref![result, 32, 32]), 4);
+        assert_eq!(&result[64..68], &[3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn read_nonexistent_account_data() {
+        let result = read_account_data(&account(0, vec![]), 0, 32);
+
+        assert_eq!(result.len(), 64);
+        assert_eq!(U256::from_be_bytes(*array_ref![result, 0, 32]), 0x20);
+        assert_eq!(U256::from_be_bytes(*array_ref![result, 32, 32]), 0);
+    }
+}
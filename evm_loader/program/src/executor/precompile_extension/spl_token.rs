@@ -22,6 +22,7 @@ use crate::{
 // [0xeb, 0x7d, 0xa7, 0x8c] : "findAccount(bytes32)",
 // [0x44, 0xef, 0x32, 0x44] : "freeze(bytes32)",
 // [0xd1, 0xde, 0x50, 0x11] : "getAccount(bytes32)",
+// [0xe7, 0x77, 0x47, 0x2f] : "getAssociatedTokenAddress(bytes32,bytes32)",
 // [0xa2, 0xce, 0x9c, 0x1f] : "getMint(bytes32)",
 // [0xda, 0xa1, 0x2c, 0x5c] : "initializeAccount(bytes32,bytes32)",
 // [0xfc, 0x86, 0xb7, 0x17] : "initializeAccount(bytes32,bytes32,bytes32)",
@@ -224,6 +225,12 @@ pub async fn spl_token<B: AccountStorage>(
             let account = read_pubkey(input)?;
             get_mint(context, state, account).await
         }
+        [0xe7, 0x77, 0x47, 0x2f] => {
+            // getAssociatedTokenAddress(bytes32 owner, bytes32 mint)
+            let owner = read_pubkey(input)?;
+            let mint = read_pubkey(&input[32..])?;
+            Ok(get_associated_token_address(&owner, &mint))
+        }
         _ => Err(Error::UnknownPrecompileMethodSelector(*address, selector)),
     }
 }
@@ -744,3 +751,24 @@ async fn get_mint<B: AccountStorage>(
 
     Ok(result.to_vec())
 }
+
+fn get_associated_token_address(owner: &Pubkey, mint: &Pubkey) -> Vec<u8> {
+    let address = spl_associated_token_account::get_associated_token_address(owner, mint);
+    address.to_bytes().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn associated_token_address() {
+        let owner = Pubkey::new_from_array([1; 32]);
+        let mint = Pubkey::new_from_array([2; 32]);
+
+        assert_eq!(
+            hex::encode(get_associated_token_address(&owner, &mint)),
+            "b0637fe98337d49a714c7f159ae0a3abb5525a699707f7cb7444b952e0f6853c"
+        );
+    }
+}
@@ -7,6 +7,7 @@ use solana_program::instruction::Instruction;
 use solana_program::pubkey::Pubkey;
 
 use crate::account_storage::AccountStorage;
+use crate::config::EXTERNAL_ACCOUNT_MAX_DATA_LEN;
 use crate::error::{Error, Result};
 use crate::evm::database::Database;
 use crate::evm::{Context, ExitStatus};
@@ -120,7 +121,7 @@ impl<'a, B: AccountStorage> ExecutorState<'a, B> {
             .collect::<Vec<_>>();
 
         if !metas.iter().any(|m| (m.pubkey == address) && m.is_writable) {
-            insert_account_if_not_present(&self.cache, address, self.backend).await;
+            insert_account_if_not_present(&self.cache, address, self.backend).await?;
             return Ok(self
                 .cache
                 .borrow()
@@ -133,7 +134,7 @@ impl<'a, B: AccountStorage> ExecutorState<'a, B> {
         let mut accounts = BTreeMap::<Pubkey, OwnedAccountInfo>::new();
 
         for m in metas {
-            insert_account_if_not_present(&self.cache, m.pubkey, self.backend).await;
+            insert_account_if_not_present(&self.cache, m.pubkey, self.backend).await?;
             accounts.insert(
                 m.pubkey,
                 self.cache
@@ -188,14 +189,45 @@ async fn insert_account_if_not_present<B: AccountStorage>(
     cache: &RefCell<Cache>,
     key: Pubkey,
     backend: &B,
-) {
+) -> Result<()> {
     if !cache.borrow().solana_accounts.contains_key(&key) {
+        check_external_account_size(&key, backend).await?;
+
         let owned_account_info = backend.clone_solana_account(&key).await;
         cache
             .borrow_mut()
             .solana_accounts
             .insert(key, owned_account_info);
     }
+
+    Ok(())
+}
+
+/// Data larger than `EXTERNAL_ACCOUNT_MAX_DATA_LEN` is not copied into the cache.
+/// Checked before the account is cloned, the same way on-chain and in emulation.
+#[maybe_async]
+async fn check_external_account_size<B: AccountStorage>(key: &Pubkey, backend: &B) -> Result<()> {
+    let program_id = backend.program_id();
+    let data_len = backend
+        .map_solana_account(key, |info| {
+            // `OwnedAccountInfo` doesn't copy the data of these accounts
+            if info.executable || (info.owner == program_id) {
+                0
+            } else {
+                info.data_len()
+            }
+        })
+        .await;
+
+    if data_len > EXTERNAL_ACCOUNT_MAX_DATA_LEN {
+        return Err(Error::ExternalAccountTooLarge(
+            *key,
+            data_len,
+            EXTERNAL_ACCOUNT_MAX_DATA_LEN,
+        ));
+    }
+
+    Ok(())
 }
 
 #[maybe_async(?Send)]
@@ -450,4 +482,37 @@ impl<'a, B: AccountStorage> Database for ExecutorState<'a, B> {
         self.call_precompile_extension(context, address, data, is_static)
             .await
     }
+
+    #[cfg(not(target_os = "solana"))]
+    fn exceeded_accounts_limit(&self) -> Option<usize> {
+        self.backend.exceeded_accounts_limit()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account_storage::test_storage::TestAccountStorage;
+
+    #[tokio::test]
+    async fn external_account_size_limit() {
+        let empty = Pubkey::new_unique();
+        let largest = Pubkey::new_unique();
+        let oversized = Pubkey::new_unique();
+
+        let mut storage = TestAccountStorage::default();
+        storage.add_solana_account(empty, vec![]);
+        storage.add_solana_account(largest, vec![0; EXTERNAL_ACCOUNT_MAX_DATA_LEN]);
+        storage.add_solana_account(oversized, vec![0; EXTERNAL_ACCOUNT_MAX_DATA_LEN + 1]);
+        let state = ExecutorState::new(&storage);
+
+        assert!(state.external_account(empty).await.is_ok());
+        assert!(state.external_account(largest).await.is_ok());
+        assert!(matches!(
+            state.external_account(oversized).await,
+            Err(Error::ExternalAccountTooLarge(key, len, EXTERNAL_ACCOUNT_MAX_DATA_LEN))
+                if key == oversized && len == EXTERNAL_ACCOUNT_MAX_DATA_LEN + 1
+        ));
+        assert!(!state.cache.borrow().solana_accounts.contains_key(&oversized));
+    }
 }
@@ -179,12 +179,8 @@ fn finalize<'a>(
 pub fn log_return_value(status: &ExitStatus) {
     use solana_program::log::sol_log_data;
 
-    let code: u8 = match status {
-        ExitStatus::Stop => 0x11,
-        ExitStatus::Return(_) => 0x12,
-        ExitStatus::Suicide => 0x13,
-        ExitStatus::Revert(_) => 0xd0,
-        ExitStatus::StepLimit => unreachable!(),
+    let Some(code) = status.code() else {
+        unreachable!()
     };
 
     solana_program::msg!("exit_status={:#04X}", code); // Tests compatibility
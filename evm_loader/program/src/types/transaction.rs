@@ -1,6 +1,7 @@
 use ethnum::U256;
 use std::convert::TryInto;
 
+use crate::config::{CHECK_BALANCE_FOR_GAS, MAX_TRANSACTION_SIZE};
 use crate::error::Error;
 
 use super::Address;
@@ -189,27 +190,7 @@ impl rlp::Decodable for AccessListTx {
         let value: U256 = u256(&rlp.at(5)?)?;
         let call_data = crate::evm::Buffer::from_slice(rlp.at(6)?.data()?);
 
-        let rlp_access_list = rlp.at(7)?;
-        let mut access_list = vec![];
-
-        for entry in rlp_access_list.iter() {
-            // Check if entry is a list
-            if entry.is_list() {
-                // Parse address from first element
-                let address: Address = entry.at(0)?.as_val()?;
-
-                // Get storage keys from second element
-                let mut storage_keys: Vec<StorageKey> = vec![];
-
-                for key in entry.at(1)?.iter() {
-                    storage_keys.push(key.as_val()?);
-                }
-
-                access_list.push((address, storage_keys));
-            } else {
-                return Err(rlp::DecoderError::RlpExpectedToBeList);
-            }
-        }
+        let access_list = access_list(&rlp.at(7)?)?;
 
         let y_parity: u8 = rlp.at(8)?.as_val()?;
         let r: U256 = u256(&rlp.at(9)?)?;
@@ -237,13 +218,95 @@ impl rlp::Decodable for AccessListTx {
     }
 }
 
-// TODO: Will be added as a part of EIP-1559
-// struct DynamicFeeTx {}
+/// EIP-1559 transaction
+/// Neon EVM has no base fee, so `max_fee_per_gas` is the gas price paid by the sender
+#[derive(Debug, Clone)]
+pub struct DynamicFeeTx {
+    pub nonce: u64,
+    pub max_priority_fee_per_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub gas_limit: U256,
+    pub target: Option<Address>,
+    pub value: U256,
+    pub call_data: crate::evm::Buffer,
+    pub r: U256,
+    pub s: U256,
+    pub chain_id: U256,
+    pub recovery_id: u8,
+    pub access_list: Vec<(Address, Vec<StorageKey>)>,
+}
+
+impl rlp::Decodable for DynamicFeeTx {
+    fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+        let rlp_len = {
+            let info = rlp.payload_info()?;
+            info.header_len + info.value_len
+        };
+
+        if rlp.as_raw().len() != rlp_len {
+            return Err(rlp::DecoderError::RlpInconsistentLengthAndData);
+        }
+
+        let chain_id: U256 = u256(&rlp.at(0)?)?;
+        let nonce: u64 = rlp.val_at(1)?;
+        let max_priority_fee_per_gas: U256 = u256(&rlp.at(2)?)?;
+        let max_fee_per_gas: U256 = u256(&rlp.at(3)?)?;
+        let gas_limit: U256 = u256(&rlp.at(4)?)?;
+        let target: Option<Address> = {
+            let target = rlp.at(5)?;
+            if target.is_empty() {
+                if target.is_data() {
+                    None
+                } else {
+                    return Err(rlp::DecoderError::RlpExpectedToBeData);
+                }
+            } else {
+                Some(target.as_val()?)
+            }
+        };
+
+        let value: U256 = u256(&rlp.at(6)?)?;
+        let call_data = crate::evm::Buffer::from_slice(rlp.at(7)?.data()?);
+        let access_list = access_list(&rlp.at(8)?)?;
+
+        let y_parity: u8 = rlp.at(9)?.as_val()?;
+        let r: U256 = u256(&rlp.at(10)?)?;
+        let s: U256 = u256(&rlp.at(11)?)?;
+
+        if rlp.at(12).is_ok() {
+            return Err(rlp::DecoderError::RlpIncorrectListLen);
+        }
+
+        if max_priority_fee_per_gas > max_fee_per_gas {
+            return Err(rlp::DecoderError::Custom(
+                "max_priority_fee_per_gas > max_fee_per_gas",
+            ));
+        }
+
+        let tx = DynamicFeeTx {
+            nonce,
+            max_priority_fee_per_gas,
+            max_fee_per_gas,
+            gas_limit,
+            target,
+            value,
+            call_data,
+            r,
+            s,
+            chain_id,
+            recovery_id: y_parity,
+            access_list,
+        };
+
+        Ok(tx)
+    }
+}
 
 #[derive(Debug, Clone)]
 pub enum TransactionPayload {
     Legacy(LegacyTx),
     AccessList(AccessListTx),
+    DynamicFee(DynamicFeeTx),
 }
 
 #[derive(Debug, Clone)]
@@ -278,6 +341,14 @@ impl Transaction {
 
                 (hash, signed_hash)
             }
+            // Dynamic Fee transaction
+            Some(TransactionEnvelope::DynamicFee) => {
+                let hash =
+                    solana_program::keccak::hashv(&[&[0x02], transaction_rlp.as_raw()]).to_bytes();
+                let signed_hash = Self::eip2718_signed_hash(&[0x02], transaction_rlp, 9)?;
+
+                (hash, signed_hash)
+            }
             // Legacy trasaction
             None => {
                 let hash = solana_program::keccak::hash(transaction_rlp.as_raw()).to_bytes();
@@ -285,7 +356,6 @@ impl Transaction {
 
                 (hash, signed_hash)
             }
-            _ => unimplemented!(),
         };
 
         let info = transaction_rlp.payload_info()?;
@@ -411,6 +481,10 @@ impl Transaction {
 
 impl Transaction {
     pub fn from_rlp(transaction: &[u8]) -> Result<Self, Error> {
+        if transaction.len() > MAX_TRANSACTION_SIZE {
+            return Err(Error::TransactionTooLarge(transaction.len(), MAX_TRANSACTION_SIZE));
+        }
+
         let (transaction_type, transaction) = TransactionEnvelope::get_type(transaction);
 
         let tx = match transaction_type {
@@ -443,30 +517,38 @@ impl Transaction {
                 let tx = TransactionPayload::Legacy(legacy_tx);
                 Transaction::from_payload(&None, chain_id, &rlp::Rlp::new(transaction), tx)?
             }
-            Some(TransactionEnvelope::DynamicFee) => unimplemented!(),
+            Some(TransactionEnvelope::DynamicFee) => {
+                let dynamic_fee_tx =
+                    rlp::decode::<DynamicFeeTx>(transaction).map_err(Error::from)?;
+                let chain_id = dynamic_fee_tx.chain_id;
+                let tx = TransactionPayload::DynamicFee(dynamic_fee_tx);
+                Transaction::from_payload(
+                    &Some(TransactionEnvelope::DynamicFee),
+                    Some(chain_id),
+                    &rlp::Rlp::new(transaction),
+                    tx,
+                )?
+            }
         };
 
         Ok(tx)
     }
 
     pub fn recover_caller_address(&self) -> Result<Address, Error> {
-        use solana_program::keccak::{hash, Hash};
-        use solana_program::secp256k1_recover::secp256k1_recover;
-
-        let signature = [self.r().to_be_bytes(), self.s().to_be_bytes()].concat();
-        let public_key = secp256k1_recover(&self.signed_hash(), self.recovery_id(), &signature)?;
+        let mut signature = [0_u8; 65];
+        signature[0..32].copy_from_slice(&self.r().to_be_bytes());
+        signature[32..64].copy_from_slice(&self.s().to_be_bytes());
+        signature[64] = self.recovery_id();
 
-        let Hash(address) = hash(&public_key.to_bytes());
-        let address: [u8; 20] = address[12..32].try_into()?;
-
-        Ok(Address::from(address))
+        recover_sender(&signature, &self.signed_hash())
     }
 
     #[must_use]
     pub fn nonce(&self) -> u64 {
         match self.transaction {
             TransactionPayload::Legacy(LegacyTx { nonce, .. })
-            | TransactionPayload::AccessList(AccessListTx { nonce, .. }) => nonce,
+            | TransactionPayload::AccessList(AccessListTx { nonce, .. })
+            | TransactionPayload::DynamicFee(DynamicFeeTx { nonce, .. }) => nonce,
         }
     }
 
@@ -475,6 +557,7 @@ impl Transaction {
         match self.transaction {
             TransactionPayload::Legacy(LegacyTx { gas_price, .. })
             | TransactionPayload::AccessList(AccessListTx { gas_price, .. }) => gas_price,
+            TransactionPayload::DynamicFee(DynamicFeeTx { max_fee_per_gas, .. }) => max_fee_per_gas,
         }
     }
 
@@ -482,15 +565,40 @@ impl Transaction {
     pub fn gas_limit(&self) -> U256 {
         match self.transaction {
             TransactionPayload::Legacy(LegacyTx { gas_limit, .. })
-            | TransactionPayload::AccessList(AccessListTx { gas_limit, .. }) => gas_limit,
+            | TransactionPayload::AccessList(AccessListTx { gas_limit, .. })
+            | TransactionPayload::DynamicFee(DynamicFeeTx { gas_limit, .. }) => gas_limit,
+        }
+    }
+
+    /// Balance the sender needs to pay for the value and the whole gas limit
+    #[must_use]
+    pub fn required_balance(&self) -> U256 {
+        self.gas_limit()
+            .saturating_mul(self.gas_price())
+            .saturating_add(self.value())
+    }
+
+    /// Rejects the transaction before execution if `balance` of the `sender` can't cover
+    /// the transferred value, and the fee too if `CHECK_BALANCE_FOR_GAS` is enabled
+    pub fn validate_sender_balance(&self, sender: Address, balance: U256) -> Result<(), Error> {
+        if balance < self.value() {
+            return Err(Error::InsufficientBalance(sender, self.value()));
+        }
+
+        let required_balance = self.required_balance();
+        if CHECK_BALANCE_FOR_GAS && balance < required_balance {
+            return Err(Error::InsufficientBalanceForFee(sender, required_balance));
         }
+
+        Ok(())
     }
 
     #[must_use]
     pub fn target(&self) -> Option<Address> {
         match self.transaction {
             TransactionPayload::Legacy(LegacyTx { target, .. })
-            | TransactionPayload::AccessList(AccessListTx { target, .. }) => target,
+            | TransactionPayload::AccessList(AccessListTx { target, .. })
+            | TransactionPayload::DynamicFee(DynamicFeeTx { target, .. }) => target,
         }
     }
 
@@ -498,7 +606,8 @@ impl Transaction {
     pub fn value(&self) -> U256 {
         match self.transaction {
             TransactionPayload::Legacy(LegacyTx { value, .. })
-            | TransactionPayload::AccessList(AccessListTx { value, .. }) => value,
+            | TransactionPayload::AccessList(AccessListTx { value, .. })
+            | TransactionPayload::DynamicFee(DynamicFeeTx { value, .. }) => value,
         }
     }
 
@@ -506,7 +615,8 @@ impl Transaction {
     pub fn call_data(&self) -> &crate::evm::Buffer {
         match &self.transaction {
             TransactionPayload::Legacy(LegacyTx { call_data, .. })
-            | TransactionPayload::AccessList(AccessListTx { call_data, .. }) => call_data,
+            | TransactionPayload::AccessList(AccessListTx { call_data, .. })
+            | TransactionPayload::DynamicFee(DynamicFeeTx { call_data, .. }) => call_data,
         }
     }
 
@@ -519,6 +629,9 @@ impl Transaction {
             })
             | TransactionPayload::AccessList(AccessListTx {
                 ref mut call_data, ..
+            })
+            | TransactionPayload::DynamicFee(DynamicFeeTx {
+                ref mut call_data, ..
             }) => std::mem::take(call_data),
         }
     }
@@ -527,7 +640,8 @@ impl Transaction {
     pub fn r(&self) -> U256 {
         match self.transaction {
             TransactionPayload::Legacy(LegacyTx { r, .. })
-            | TransactionPayload::AccessList(AccessListTx { r, .. }) => r,
+            | TransactionPayload::AccessList(AccessListTx { r, .. })
+            | TransactionPayload::DynamicFee(DynamicFeeTx { r, .. }) => r,
         }
     }
 
@@ -535,7 +649,8 @@ impl Transaction {
     pub fn s(&self) -> U256 {
         match self.transaction {
             TransactionPayload::Legacy(LegacyTx { s, .. })
-            | TransactionPayload::AccessList(AccessListTx { s, .. }) => s,
+            | TransactionPayload::AccessList(AccessListTx { s, .. })
+            | TransactionPayload::DynamicFee(DynamicFeeTx { s, .. }) => s,
         }
     }
 
@@ -543,7 +658,8 @@ impl Transaction {
     pub fn chain_id(&self) -> Option<U256> {
         match self.transaction {
             TransactionPayload::Legacy(LegacyTx { chain_id, .. }) => chain_id,
-            TransactionPayload::AccessList(AccessListTx { chain_id, .. }) => Some(chain_id),
+            TransactionPayload::AccessList(AccessListTx { chain_id, .. })
+            | TransactionPayload::DynamicFee(DynamicFeeTx { chain_id, .. }) => Some(chain_id),
         }
     }
 
@@ -551,7 +667,8 @@ impl Transaction {
     pub fn recovery_id(&self) -> u8 {
         match self.transaction {
             TransactionPayload::Legacy(LegacyTx { recovery_id, .. })
-            | TransactionPayload::AccessList(AccessListTx { recovery_id, .. }) => recovery_id,
+            | TransactionPayload::AccessList(AccessListTx { recovery_id, .. })
+            | TransactionPayload::DynamicFee(DynamicFeeTx { recovery_id, .. }) => recovery_id,
         }
     }
 
@@ -573,12 +690,64 @@ impl Transaction {
     #[must_use]
     pub fn access_list(&self) -> Option<&Vec<(Address, Vec<StorageKey>)>> {
         match &self.transaction {
-            TransactionPayload::AccessList(AccessListTx { access_list, .. }) => Some(access_list),
+            TransactionPayload::AccessList(AccessListTx { access_list, .. })
+            | TransactionPayload::DynamicFee(DynamicFeeTx { access_list, .. }) => Some(access_list),
             TransactionPayload::Legacy(_) => None,
         }
     }
 }
 
+/// Recover the address which signed `message` hash.
+/// `signature` is `r || s || recovery_id`
+pub fn recover_sender(signature: &[u8; 65], message: &[u8]) -> Result<Address, Error> {
+    use solana_program::keccak::{hash, Hash};
+    use solana_program::secp256k1_recover::secp256k1_recover;
+
+    let public_key = secp256k1_recover(message, signature[64], &signature[0..64])?;
+
+    let Hash(address) = hash(&public_key.to_bytes());
+    let address: [u8; 20] = address[12..32].try_into()?;
+
+    Ok(Address::from(address))
+}
+
+fn access_list(rlp: &rlp::Rlp) -> Result<Vec<(Address, Vec<StorageKey>)>, rlp::DecoderError> {
+    let mut access_list = vec![];
+
+    if !rlp.is_list() {
+        return Err(rlp::DecoderError::RlpExpectedToBeList);
+    }
+
+    for entry in rlp.iter() {
+        // Check if entry is a list of [address, [storage keys]]
+        if !entry.is_list() {
+            return Err(rlp::DecoderError::RlpExpectedToBeList);
+        }
+        if entry.item_count()? != 2 {
+            return Err(rlp::DecoderError::RlpIncorrectListLen);
+        }
+
+        // Parse address from first element
+        let address: Address = entry.at(0)?.as_val()?;
+
+        // Get storage keys from second element
+        let keys = entry.at(1)?;
+        if !keys.is_list() {
+            return Err(rlp::DecoderError::RlpExpectedToBeList);
+        }
+
+        let mut storage_keys: Vec<StorageKey> = vec![];
+
+        for key in keys.iter() {
+            storage_keys.push(key.as_val()?);
+        }
+
+        access_list.push((address, storage_keys));
+    }
+
+    Ok(access_list)
+}
+
 #[inline]
 fn u256(rlp: &rlp::Rlp) -> Result<U256, rlp::DecoderError> {
     rlp.decoder().decode_value(|bytes| {
@@ -593,3 +762,134 @@ fn u256(rlp: &rlp::Rlp) -> Result<U256, rlp::DecoderError> {
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oversized_transaction_is_rejected_before_decode() {
+        let message = vec![0_u8; MAX_TRANSACTION_SIZE + 1];
+
+        let result = Transaction::from_rlp(&message);
+        assert!(matches!(
+            result,
+            Err(Error::TransactionTooLarge(size, MAX_TRANSACTION_SIZE)) if size == message.len()
+        ));
+    }
+
+    #[test]
+    fn recover_eip155_example_sender() {
+        // https://eips.ethereum.org/EIPS/eip-155#example
+        let message = hex::decode(
+            "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a7640000\
+             8025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f\
+             761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83",
+        )
+        .unwrap();
+        let trx = Transaction::from_rlp(&message).unwrap();
+
+        let expected = Address::from_hex("0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f").unwrap();
+        assert_eq!(trx.recover_caller_address().unwrap(), expected);
+
+        let mut signature = [0_u8; 65];
+        signature[0..32].copy_from_slice(&trx.r().to_be_bytes());
+        signature[32..64].copy_from_slice(&trx.s().to_be_bytes());
+        signature[64] = trx.recovery_id();
+        assert_eq!(recover_sender(&signature, &trx.signed_hash()).unwrap(), expected);
+    }
+
+    #[test]
+    fn sender_without_fee_funds_is_rejected() {
+        // https://eips.ethereum.org/EIPS/eip-155#example
+        let message = hex::decode(
+            "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a7640000\
+             8025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f\
+             761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83",
+        )
+        .unwrap();
+        let trx = Transaction::from_rlp(&message).unwrap();
+        let sender = Address::from([0x9d; 20]);
+
+        // 1 ether + 21000 gas * 20 gwei
+        let required = U256::new(1_000_420_000_000_000_000);
+        assert_eq!(trx.required_balance(), required);
+
+        assert!(trx.validate_sender_balance(sender, required).is_ok());
+        assert!(matches!(
+            trx.validate_sender_balance(sender, trx.value()),
+            Err(Error::InsufficientBalanceForFee(address, value))
+                if address == sender && value == required
+        ));
+        assert!(matches!(
+            trx.validate_sender_balance(sender, U256::ZERO),
+            Err(Error::InsufficientBalance(..))
+        ));
+    }
+
+    #[test]
+    fn decode_dynamic_fee_transaction() {
+        // EIP-1559 transfer signed with the EIP-155 example key
+        let message = hex::decode(
+            "02f8730109843b9aca008504a817c800825208943535353535353535353535353535353535353535\
+             880de0b6b3a764000080c080a0bb50e2d89a4ed70663d080659fe0ad4b9bc3e06c17a227433966cb\
+             59ceee020da04f5b1f9aa5dae61d7834a04712b4c6605bc09de9c968f2e9191ecd1a26aa3bd7",
+        )
+        .unwrap();
+        let trx = Transaction::from_rlp(&message).unwrap();
+
+        assert!(matches!(trx.transaction, TransactionPayload::DynamicFee(_)));
+        assert_eq!(trx.chain_id(), Some(U256::ONE));
+        assert_eq!(trx.nonce(), 9);
+        assert_eq!(trx.gas_price(), U256::new(20_000_000_000));
+        assert_eq!(trx.gas_limit(), U256::new(21_000));
+        assert_eq!(trx.target(), Some(Address::from([0x35; 20])));
+        assert_eq!(trx.value(), U256::new(1_000_000_000_000_000_000));
+        assert_eq!(trx.access_list().map(Vec::len), Some(0));
+        assert_eq!(trx.rlp_len(), message.len());
+        assert_eq!(
+            hex::encode(trx.signed_hash()),
+            "577f072b4be21dbe73cdd90f32675d67d2fdfefdecfbc579f52025caf096400a"
+        );
+
+        let expected = Address::from_hex("0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f").unwrap();
+        assert_eq!(trx.recover_caller_address().unwrap(), expected);
+    }
+
+    #[test]
+    fn malformed_access_list_is_rejected() {
+        let mut address = vec![0x94_u8];
+        address.extend_from_slice(&[0x35; 20]);
+
+        // [address, [], 0x01] has an extra item
+        let mut entry = vec![0xd7];
+        entry.extend_from_slice(&address);
+        entry.extend_from_slice(&[0xc0, 0x01]);
+        let mut rlp = vec![0xd8];
+        rlp.extend_from_slice(&entry);
+        assert_eq!(
+            access_list(&rlp::Rlp::new(&rlp)),
+            Err(rlp::DecoderError::RlpIncorrectListLen)
+        );
+
+        // [address, 0x01] has no storage keys list
+        let mut entry = vec![0xd6];
+        entry.extend_from_slice(&address);
+        entry.push(0x01);
+        let mut rlp = vec![0xd7];
+        rlp.extend_from_slice(&entry);
+        assert_eq!(
+            access_list(&rlp::Rlp::new(&rlp)),
+            Err(rlp::DecoderError::RlpExpectedToBeList)
+        );
+
+        // [address, []] is well formed
+        let mut entry = vec![0xd6];
+        entry.extend_from_slice(&address);
+        entry.push(0xc0);
+        let mut rlp = vec![0xd7];
+        rlp.extend_from_slice(&entry);
+        let decoded = access_list(&rlp::Rlp::new(&rlp)).unwrap();
+        assert_eq!(decoded, vec![(Address::from([0x35; 20]), vec![])]);
+    }
+}
@@ -8,12 +8,14 @@ from solana.rpc.commitment import Confirmed
 
 from .solana_utils import neon_cli, create_treasury_pool_address, get_neon_balance, get_transaction_count
 from .solana_utils import solana_client, wait_confirm_transaction, get_solana_balance, send_transaction
+from .solana_utils import write_transaction_to_holder_account
 from .utils.constants import SOLANA_URL
 from .utils.contract import deploy_contract
 from .utils.ethereum import make_eth_transaction
-from eth_utils import abi, to_text
+from eth_utils import abi, keccak, to_text
 
-from .utils.instructions import TransactionWithComputeBudget, make_PartialCallOrContinueFromRawEthereumTX
+from .utils.instructions import TransactionWithComputeBudget, make_PartialCallOrContinueFromRawEthereumTX, \
+    make_WriteHolder
 from .utils.storage import create_holder
 
 
@@ -172,3 +174,58 @@ def test_cancel_trx(evm_loader, user_account, rw_lock_contract, operator_keypair
     result = neon_cli().call(f"cancel-trx --evm_loader={evm_loader.loader_id} {storage_account}")
     assert result["transaction"] is not None
     assert user_nonce < get_transaction_count(solana_client, user_account.solana_account_address)
+
+
+def make_holder_transaction(user_account, rw_lock_contract):
+    func_name = abi.function_signature_to_4byte_selector('unchange_storage(uint8,uint8)')
+    data = (func_name + bytes.fromhex("%064x" % 0x01) + bytes.fromhex("%064x" % 0x01))
+
+    eth_transaction = make_eth_transaction(
+        rw_lock_contract.eth_address,
+        data,
+        user_account.solana_account,
+        user_account.solana_account_address,
+    )
+    return eth_transaction, data
+
+
+def test_get_holder(evm_loader, user_account, rw_lock_contract, operator_keypair):
+    eth_transaction, data = make_holder_transaction(user_account, rw_lock_contract)
+    holder_account = create_holder(operator_keypair)
+    write_transaction_to_holder_account(eth_transaction, holder_account, operator_keypair)
+
+    result = neon_cli().call(f"get-holder --evm_loader={evm_loader.loader_id} {holder_account}")
+    assert result["owner"] == str(operator_keypair.public_key)
+    assert result["transaction_hash"] == bytes(eth_transaction.hash).hex()
+    assert result["written_len"] == len(eth_transaction.rawTransaction)
+    assert result["expected_len"] == len(eth_transaction.rawTransaction)
+    assert result["transaction"]["from"] == f"0x{user_account.eth_address.hex()}"
+    assert result["transaction"]["to"] == f"0x{rw_lock_contract.eth_address.hex()}"
+    assert result["transaction"]["nonce"] == get_transaction_count(solana_client, user_account.solana_account_address)
+    assert result["transaction"]["value"] == "0"
+    assert result["transaction"]["call_data"] == data.hex()
+
+
+def test_get_holder_partially_written(evm_loader, user_account, rw_lock_contract, operator_keypair):
+    eth_transaction, _ = make_holder_transaction(user_account, rw_lock_contract)
+    holder_account = create_holder(operator_keypair)
+
+    part = eth_transaction.rawTransaction[:32]
+    trx = TransactionWithComputeBudget(operator_keypair)
+    trx.add(make_WriteHolder(operator_keypair.public_key, holder_account, eth_transaction.hash, 0, part))
+    send_transaction(solana_client, trx, operator_keypair)
+
+    result = neon_cli().call(f"get-holder --evm_loader={evm_loader.loader_id} {holder_account}")
+    assert result["written_len"] == len(part)
+    assert result["expected_len"] == len(eth_transaction.rawTransaction)
+    assert result["transaction"] is None
+
+
+def test_prepare_deploy(evm_loader, user_account):
+    result = neon_cli().call(
+        f"prepare-deploy --evm_loader {evm_loader.loader_id} {user_account.eth_address.hex()} --salt 0x0")
+
+    sender = user_account.eth_address
+    expected_address = keccak(b"\xff" + sender + bytes(32) + keccak(b""))[12:]
+    assert result["contract_address"] == f"0x{expected_address.hex()}"
+    assert result["required_accounts"] == [str(user_account.solana_account_address), result["contract_account"]]
@@ -22,6 +22,11 @@ def test_get_ether_account_data(neon_api_client, user_account):
     assert solana_client.get_account_info(user_account.solana_account.public_key).value is not None
 
 
+def test_prepare_deploy(neon_api_client, user_account):
+    result = neon_api_client.prepare_deploy(user_account.eth_address.hex())["value"]
+    assert result["required_accounts"] == [str(user_account.solana_account_address), result["contract_account"]]
+
+
 def test_emulate_transfer(neon_api_client, user_account, session_user):
     result = neon_api_client.emulate(user_account.eth_address.hex(),
                                      session_user.eth_address.hex())["value"]
@@ -42,6 +47,19 @@ def test_emulate_contract_deploy(neon_api_client, user_account):
     assert result['used_gas'] > 0, f"Used gas is less than 0. Result: {result}"
 
 
+def test_emulate_contract_deploy_created_address(neon_api_client, operator_keypair, treasury_pool, evm_loader,
+                                                 user_account):
+    contract_path = pytest.CONTRACTS_PATH / "hello_world.binary"
+
+    with open(contract_path, 'rb') as f:
+        contract_code = f.read()
+    result = neon_api_client.emulate(user_account.eth_address.hex(),
+                                     contract=None, data=contract_code)["value"]
+
+    contract = deploy_contract(operator_keypair, user_account, "hello_world.binary", evm_loader, treasury_pool)
+    assert result['created_address'] == f"0x{contract.eth_address.hex()}", f"Result: {result}"
+
+
 def test_emulate_call_contract_function(neon_api_client, operator_keypair, treasury_pool, evm_loader, user_account):
     contract = deploy_contract(operator_keypair, user_account, "hello_world.binary", evm_loader, treasury_pool)
     assert contract.eth_address
@@ -33,3 +33,12 @@ class NeonApiClient:
 
     def get_ether_account_data(self, ether):
         return requests.get(f"{self.url}/get-ether-account-data?ether={ether}").json()
+
+    def prepare_deploy(self, sender, data=None, salt=None):
+        body = {"sender": sender}
+        if data:
+            body["data"] = list(data)
+        if salt is not None:
+            body["salt"] = salt
+        resp = requests.post(url=f"{self.url}/prepare-deploy", json=body, headers=self.headers)
+        return resp.json()